@@ -0,0 +1,140 @@
+//! Minimal PSP22 token for the polkapobal end-to-end tests.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+type Balance = <ink::env::DefaultEnvironment as ink::env::Environment>::Balance;
+
+#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+#[ink::trait_definition]
+pub trait PSP22 {
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> Balance;
+
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>)
+        -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+}
+
+#[ink::contract]
+mod psp22_mock {
+    use super::{PSP22Error, PSP22};
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    pub struct Psp22Mock {
+        balances: Mapping<AccountId, Balance>,
+        // (owner, spender) -> allowance
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        // Whether transfers fail, to mimic a broken or paused token
+        failing: bool,
+    }
+
+    impl Psp22Mock {
+        /// Mints `supply` tokens to the caller.
+        #[ink(constructor)]
+        pub fn new(supply: Balance) -> Self {
+            let mut balances = Mapping::default();
+            balances.insert(Self::env().caller(), &supply);
+            Self {
+                balances,
+                allowances: Mapping::default(),
+                failing: false,
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_failing(&mut self, failing: bool) {
+            self.failing = failing;
+        }
+
+        fn move_tokens(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), PSP22Error> {
+            if self.failing {
+                return Err(PSP22Error::Custom(String::from("Token is failing")));
+            }
+
+            let from_balance = self.balances.get(from).unwrap_or(0);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balances.get(to).unwrap_or(0);
+            self.balances.insert(to, &(to_balance + value));
+            Ok(())
+        }
+    }
+
+    impl PSP22 for Psp22Mock {
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.allowances
+                .insert((self.env().caller(), spender), &value);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.move_tokens(self.env().caller(), to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let key = (from, self.env().caller());
+            let allowance = self.allowances.get(key).unwrap_or(0);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.move_tokens(from, to, value)?;
+            self.allowances.insert(key, &(allowance - value));
+            Ok(())
+        }
+    }
+}
+
+pub use psp22_mock::{Psp22Mock, Psp22MockRef};
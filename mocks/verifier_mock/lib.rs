@@ -0,0 +1,40 @@
+//! Minimal proof verifier for the polkapobal end-to-end tests.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::string::String;
+use ink::primitives::{AccountId, Hash};
+
+#[ink::trait_definition]
+pub trait ProofVerifier {
+    #[ink(message)]
+    fn verify(&self, task_id: String, participant: AccountId, proof: Hash) -> bool;
+}
+
+#[ink::contract]
+mod verifier_mock {
+    use super::ProofVerifier;
+    use ink::prelude::string::String;
+
+    #[ink(storage)]
+    pub struct VerifierMock {
+        // The only proof this verifier accepts
+        valid_proof: Hash,
+    }
+
+    impl VerifierMock {
+        #[ink(constructor)]
+        pub fn new(valid_proof: Hash) -> Self {
+            Self { valid_proof }
+        }
+    }
+
+    impl ProofVerifier for VerifierMock {
+        #[ink(message)]
+        fn verify(&self, _task_id: String, _participant: AccountId, proof: Hash) -> bool {
+            proof == self.valid_proof
+        }
+    }
+}
+
+pub use verifier_mock::{VerifierMock, VerifierMockRef};
@@ -1,14 +1,292 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub mod proof_verifier;
+mod psp22;
+mod psp34;
+
 #[ink::contract]
 mod polkapobal {
-    use ink::{prelude::string::String, prelude::vec::Vec, storage::Mapping};
+    use crate::proof_verifier;
+    use crate::psp22;
+    use crate::psp34::{self, Id, PSP34Error};
+    use ink::{
+        prelude::{string::String, string::ToString, vec::Vec},
+        storage::Mapping,
+    };
+    use primitive_types::U256;
+
+    /// Number of members selected to work on the task of an era.
+    pub const PARTICIPANTS_PER_ERA: usize = 4;
+
+    /// Number of past groups kept for `SelectionStrategy::SocialMixing` by default.
+    pub const DEFAULT_GROUP_HISTORY_LIMIT: u32 = 50;
+
+    /// Maximum number of tags a single task can carry.
+    pub const MAX_TAGS_PER_TASK: usize = 5;
+
+    /// Maximum number of skills a member can declare or a task can require.
+    pub const MAX_SKILLS: usize = 10;
+
+    /// Maximum length in bytes of the URI attached to a completion proof.
+    pub const MAX_PROOF_URI_LEN: usize = 256;
+
+    /// Maximum number of oracles that can attest the proofs of a single task.
+    pub const MAX_TASK_ORACLES: usize = 5;
+
+    /// Maximum share of a paid out bounty, in basis points, that goes to the keeper pool.
+    pub const MAX_KEEPER_FEE_BPS: u32 = 500;
+
+    /// Maximum tip paid from the keeper pool for a single keeper call.
+    pub const MAX_KEEPER_TIP: Balance = 1_000_000_000_000;
+
+    /// Block time assumed to convert between block numbers and timestamps.
+    pub const EXPECTED_BLOCK_TIME_MS: u64 = 6_000;
+
+    /// Token bounties of a task as `(token, funds)` pairs.
+    pub type TokenFunds = Vec<(AccountId, Balance)>;
+
+    /// Contribution of a donor to a task as `(matching round, amount)`.
+    pub type RoundContribution = (u32, Balance);
+
+    /// Token donation of a donor as `(funding round, donor, token)`.
+    pub type TokenDonationKey = (u32, AccountId, AccountId);
+
+    /// Compressed secp256k1 public key of an attestation oracle.
+    pub type OracleKey = [u8; 33];
+
+    /// Availability window of a task as `(available_from, expires_at)` blocks.
+    pub type TaskWindow = (Option<u32>, Option<u32>);
+
+    /// Funding goal of a task as `(target, cap)` native bounties.
+    pub type FundingGoal = (Option<Balance>, Option<Balance>);
+
+    /// Conditional donation to a task as `(amount, deadline)`.
+    pub type Pledge = (Balance, u32);
+
+    /// Proposed task as `(proposer, approving members)`.
+    pub type Proposal = (AccountId, Vec<AccountId>);
+
+    /// Whether a task returns to the pool after it has been completed.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Recurrence {
+        /// The task is done after its first completion.
+        Once,
+        /// The task reopens `after_blocks` after each completion, until it has been
+        /// completed `max_occurrences` times if set.
+        Repeat {
+            after_blocks: u32,
+            max_occurrences: Option<u32>,
+        },
+    }
+
+    /// A completed run of a task.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Occurrence {
+        /// Block number at which the era started.
+        era: u32,
+        /// Block number at which the task was completed.
+        completed_at: u32,
+        /// Distinct members who completed the task.
+        participants: Vec<AccountId>,
+        /// Completion proofs uploaded by the participants as `(participant, hash)`.
+        proofs: Vec<(AccountId, Hash)>,
+        /// Native bounty paid out to the participants.
+        funds: Balance,
+    }
+
+    /// Members working on one task during an era.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Group {
+        /// The task assigned to the group.
+        task: String,
+        /// Selected members, repeated if there were fewer candidates than slots.
+        participants: Vec<AccountId>,
+        /// Whether the owner marked the task as completed.
+        is_complete: bool,
+        /// Participants who have yet to accept the assignment. The group is active
+        /// once this is empty.
+        pending: Vec<AccountId>,
+    }
+
+    /// Non-transferable PSP34 token recording a member's part in a completed era.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Badge {
+        /// The completed task.
+        task: String,
+        /// Block number at which the era started.
+        era: u32,
+        /// Completion proof the member uploaded for the era, if any.
+        proof: Option<Hash>,
+    }
+
+    /// What a completion proof consists of.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ProofKind {
+        Photo,
+        Document,
+        GitCommit,
+        Attestation,
+    }
+
+    /// Completion proof uploaded by a participant.
+    #[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Proof {
+        /// The task the proof is for.
+        task: String,
+        /// Hash of the proof's content.
+        hash: Hash,
+        /// Where the content can be found, e.g. an IPFS CID.
+        uri: Option<String>,
+        kind: ProofKind,
+        /// Timestamp of the upload in milliseconds.
+        timestamp: u64,
+        /// Whether the task's verifier contract approved the proof.
+        approved: bool,
+    }
+
+    /// Which completion proofs a task needs before it can be completed.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ProofRule {
+        /// Proofs are optional.
+        Optional,
+        /// At least one participant has to upload a proof.
+        PerGroup,
+        /// Every participant has to upload a proof.
+        PerParticipant,
+    }
+
+    /// When eras can start.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Schedule {
+        /// `next_selection` blocks after the previous era started.
+        Blocks,
+        /// At the first timestamp after the previous era started that is `offset`
+        /// milliseconds past a multiple of `period`, e.g. every Monday 12:00 UTC with
+        /// a period of one week and an offset of 4 days and 12 hours.
+        Timestamp { period: u64, offset: u64 },
+    }
+
+    /// How `start_new_era` picks participants among the qualified members.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum SelectionStrategy {
+        /// Pick members by block number.
+        Random,
+        /// Pick the members that have waited longest since their last selection, so
+        /// every member is picked at least once per `members / PARTICIPANTS_PER_ERA`
+        /// eras (rounded up).
+        Fair,
+        /// Pick the group whose members were grouped together the fewest times in the
+        /// recorded group history.
+        SocialMixing,
+    }
+
+    /// How `randomly_select_task` weighs the tasks it draws from.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum TaskWeighting {
+        /// Every task has the same odds.
+        Uniform,
+        /// Odds proportional to the native bounty of the task.
+        Bounty,
+        /// Odds growing with the number of eras since the task was added or last
+        /// selected.
+        Age,
+        /// Odds proportional to the number of member votes for the task.
+        Votes,
+    }
+
+    /// Groups of operations that can be paused independently.
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PauseCategory {
+        /// `fund_task` and `fund_task_with_token`
+        Funding,
+        /// `register_member` and `add_task`
+        Membership,
+        /// `start_new_era`
+        Eras,
+        /// `complete_task`
+        Payouts,
+    }
 
     #[ink(event)]
     pub struct SelectionEraChanged {
         new_era: u32,
     }
 
+    #[ink(event)]
+    pub struct Paused {
+        /// The paused category, or `None` if the whole contract was paused.
+        category: Option<PauseCategory>,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        /// The unpaused category, or `None` if the whole contract was unpaused.
+        category: Option<PauseCategory>,
+    }
+
+    #[ink(event)]
+    pub struct SelectionStrategySet {
+        strategy: SelectionStrategy,
+    }
+
+    #[ink(event)]
+    pub struct SelectionCooldownSet {
+        eras: u32,
+    }
+
+    #[ink(event)]
+    pub struct GroupHistoryLimitSet {
+        eras: u32,
+    }
+
+    #[ink(event)]
+    pub struct TaskWeightingSet {
+        weighting: TaskWeighting,
+        /// Maximum weight of a single task, if any.
+        cap: Option<u128>,
+    }
+
     #[ink(event)]
     pub struct MemberRegistered {
         /// The member that was added.
@@ -26,6 +304,27 @@ mod polkapobal {
     #[ink(event)]
     pub struct MembersCleared {}
 
+    #[ink(event)]
+    pub struct MemberUnavailable {
+        #[ink(topic)]
+        member: AccountId,
+        /// Block from which the member is eligible for selection again.
+        until_block: u32,
+    }
+
+    #[ink(event)]
+    pub struct MemberAvailable {
+        #[ink(topic)]
+        member: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MemberSkillsSet {
+        #[ink(topic)]
+        member: AccountId,
+        skills: Vec<String>,
+    }
+
     #[ink(event)]
     pub struct TaskAdded {
         /// The task that was added.
@@ -33,6 +332,33 @@ mod polkapobal {
         task: String,
     }
 
+    #[ink(event)]
+    pub struct TaskProposed {
+        /// The task that awaits approval.
+        #[ink(topic)]
+        task: String,
+    }
+
+    #[ink(event)]
+    pub struct TaskApproved {
+        #[ink(topic)]
+        task: String,
+    }
+
+    #[ink(event)]
+    pub struct TaskRejected {
+        #[ink(topic)]
+        task: String,
+        /// Hash of the reason given by the curator.
+        reason: Hash,
+    }
+
+    #[ink(event)]
+    pub struct TaskReviewSet {
+        enabled: bool,
+        approval_threshold: u32,
+    }
+
     #[ink(event)]
     pub struct TaskRemoved {
         /// The task that was removed.
@@ -44,647 +370,5974 @@ mod polkapobal {
     pub struct TasksCleared {}
 
     #[ink(event)]
-    pub struct TaskFunded {
+    pub struct TaskVoted {
         #[ink(topic)]
         task: String,
         #[ink(topic)]
-        donor: AccountId,
-        amount: Balance,
+        member: AccountId,
     }
 
     #[ink(event)]
-    pub struct NewEraStarted {
+    pub struct TaskUnvoted {
+        #[ink(topic)]
+        task: String,
+        #[ink(topic)]
+        member: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ProofUploaded {
         era: u32,
         #[ink(topic)]
-        participants: Vec<AccountId>,
+        participant: AccountId,
         #[ink(topic)]
         task: String,
+        kind: ProofKind,
+        hash: Hash,
     }
 
-    #[ink(storage)]
-    pub struct Polkapobal {
-        owner: AccountId,
-        members: Vec<AccountId>,
-        is_member: Mapping<AccountId, ()>,
-        tasks: Vec<String>,
-        // task -> (is_complete, funds)
-        task_info: Mapping<String, (bool, Balance)>,
-        unclaimed_funds: Balance,
-        start_block: u32,
-        // How many blocks until next selection
-        next_selection: u32,
-        // Last selection block number
-        last_selection: u32,
-        active_participants: Vec<AccountId>,
-        // (task, is_complete)
-        active_task: Option<(String, bool)>,
-        // task -> proof hash
-        proofs: Mapping<String, Hash>,
+    #[ink(event)]
+    pub struct ProofVerified {
+        era: u32,
+        #[ink(topic)]
+        participant: AccountId,
+        #[ink(topic)]
+        task: String,
+        approved: bool,
     }
 
-    impl Polkapobal {
-        #[ink(constructor)]
-        pub fn new(selection_era: u32) -> Self {
-            let current_block = Self::env().block_number();
-            Polkapobal {
-                owner: Self::env().caller(),
-                members: Vec::new(),
-                is_member: Mapping::default(),
-                tasks: Vec::new(),
-                task_info: Mapping::default(),
-                unclaimed_funds: 0,
-                start_block: current_block,
-                next_selection: selection_era,
-                last_selection: current_block,
-                active_participants: Vec::new(),
-                active_task: None,
-                proofs: Mapping::default(),
-            }
-        }
-
-        #[ink(message)]
-        pub fn set_selection_era(&mut self, selection_era: u32) {
-            self.ensure_owner();
-
-            self.next_selection = selection_era;
+    #[ink(event)]
+    pub struct OracleAdded {
+        oracle: OracleKey,
+    }
 
-            self.env().emit_event(SelectionEraChanged {
-                new_era: selection_era,
-            })
-        }
+    #[ink(event)]
+    pub struct OracleRemoved {
+        oracle: OracleKey,
+    }
 
-        #[ink(message)]
-        pub fn register_member(&mut self) {
-            let caller = self.env().caller();
+    #[ink(event)]
+    pub struct AttestationAccepted {
+        era: u32,
+        #[ink(topic)]
+        participant: AccountId,
+        #[ink(topic)]
+        task: String,
+        oracle: OracleKey,
+        nonce: u64,
+    }
 
-            // Ensure that the member does not exist
-            assert!(!self.is_member.contains(&caller), "Member already exists");
+    #[ink(event)]
+    pub struct TaskVerifierSet {
+        #[ink(topic)]
+        task: String,
+        verifier: Option<AccountId>,
+    }
 
-            self.is_member.insert(caller, &());
-            self.members.push(caller);
+    #[ink(event)]
+    pub struct TaskOraclesSet {
+        #[ink(topic)]
+        task: String,
+        oracles: Vec<OracleKey>,
+    }
 
-            self.env().emit_event(MemberRegistered { member: caller });
-        }
+    #[ink(event)]
+    pub struct ProofRuleSet {
+        #[ink(topic)]
+        task: String,
+        rule: ProofRule,
+    }
 
-        #[ink(message)]
-        pub fn deregister_member(&mut self) {
-            let caller = self.env().caller();
+    #[ink(event)]
+    pub struct TaskRecurrenceSet {
+        #[ink(topic)]
+        task: String,
+        recurrence: Recurrence,
+    }
 
-            // Ensure that the member exists
-            self.ensure_member();
+    #[ink(event)]
+    pub struct TaskWindowSet {
+        #[ink(topic)]
+        task: String,
+        available_from: Option<u32>,
+        expires_at: Option<u32>,
+    }
 
-            // Search for index of member
-            let index = self
-                .members
-                .iter()
-                .position(|x| *x == caller)
-                .expect("Member existence verified before calling");
-            self.members.swap_remove(index);
-            self.is_member.remove(caller);
+    #[ink(event)]
+    pub struct FundingGoalSet {
+        #[ink(topic)]
+        task: String,
+        target: Option<Balance>,
+        cap: Option<Balance>,
+    }
 
-            self.env().emit_event(MemberDeregistered { member: caller });
-        }
+    #[ink(event)]
+    pub struct FundingGoalReached {
+        #[ink(topic)]
+        task: String,
+        funds: Balance,
+    }
 
-        #[ink(message)]
-        pub fn clear_members(&mut self) {
-            self.ensure_owner();
+    #[ink(event)]
+    pub struct FundingGoalsRequiredSet {
+        required: bool,
+    }
 
-            // Iterate over `members` vec and remove each member.
-            // Done in reverse so the member indices' do not change.
-            for (i, member) in self.members.clone().iter().enumerate().rev() {
-                self.is_member.remove(member);
-                self.members.swap_remove(i);
-            }
+    #[ink(event)]
+    pub struct MatchingRoundOpened {
+        /// Pool seeded from the unclaimed funds and the deposit of the call.
+        pool: Balance,
+    }
 
-            self.env().emit_event(MembersCleared {});
-        }
+    #[ink(event)]
+    pub struct MatchingPoolFunded {
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
 
-        #[ink(message)]
-        pub fn add_task(&mut self, task: String) {
-            self.ensure_member();
+    #[ink(event)]
+    pub struct MatchingRoundClosed {
+        /// Funds added to each task's bounty as `(task, amount)` pairs.
+        allocations: Vec<(String, Balance)>,
+        /// Part of the pool returned to the unclaimed funds.
+        unallocated: Balance,
+    }
 
-            // Ensure that the task does not exist
-            assert!(!self.task_info.contains(&task), "Task already exists");
+    #[ink(event)]
+    pub struct TaskExpired {
+        #[ink(topic)]
+        task: String,
+        /// Funding round whose donations can be claimed back, if the task was funded.
+        round: Option<u32>,
+        /// Native funds made refundable to the task's donors.
+        refunded: Balance,
+    }
 
-            self.task_info.insert(&task, &(false, 0));
-            self.tasks.push(task.clone());
+    #[ink(event)]
+    pub struct RefundClaimed {
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
 
-            self.env().emit_event(TaskAdded { task: task });
-        }
+    #[ink(event)]
+    pub struct TokenRefundClaimed {
+        #[ink(topic)]
+        donor: AccountId,
+        #[ink(topic)]
+        token: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TaskReopened {
+        #[ink(topic)]
+        task: String,
+        /// Block from which the task can be selected again.
+        available_at: u32,
+    }
+
+    #[ink(event)]
+    pub struct TaskTagsSet {
+        #[ink(topic)]
+        task: String,
+        tags: Vec<String>,
+    }
+
+    #[ink(event)]
+    pub struct TaskSkillsSet {
+        #[ink(topic)]
+        task: String,
+        skills: Vec<String>,
+    }
+
+    #[ink(event)]
+    pub struct CuratorAdded {
+        #[ink(topic)]
+        curator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CuratorRemoved {
+        #[ink(topic)]
+        curator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CategoryAdded {
+        #[ink(topic)]
+        category: String,
+    }
+
+    #[ink(event)]
+    pub struct CategoryRemoved {
+        #[ink(topic)]
+        category: String,
+    }
+
+    #[ink(event)]
+    pub struct EraCategoriesSet {
+        /// Categories the next eras draw tasks from, empty for all tasks.
+        categories: Vec<String>,
+    }
+
+    #[ink(event)]
+    pub struct TaskFunded {
+        #[ink(topic)]
+        task: String,
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+        /// Block after which the donation can be reclaimed if the task is not completed.
+        pledge_deadline: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct PledgeReclaimed {
+        #[ink(topic)]
+        task: String,
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TaskFundedWithToken {
+        #[ink(topic)]
+        task: String,
+        #[ink(topic)]
+        donor: AccountId,
+        #[ink(topic)]
+        token: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PayoutFailed {
+        #[ink(topic)]
+        participant: AccountId,
+        /// Share the participant can claim with `claim_payout` instead.
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TokenPayoutFailed {
+        #[ink(topic)]
+        token: AccountId,
+        #[ink(topic)]
+        participant: AccountId,
+        /// Share the participant can claim with `claim_token_payout` instead.
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PayoutClaimed {
+        #[ink(topic)]
+        participant: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TokenPayoutClaimed {
+        #[ink(topic)]
+        participant: AccountId,
+        #[ink(topic)]
+        token: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AcceptedTokenAdded {
+        #[ink(topic)]
+        token: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AcceptedTokenRemoved {
+        #[ink(topic)]
+        token: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct NewEraStarted {
+        era: u32,
+        /// Index of the group in the era's active groups.
+        group: u32,
+        #[ink(topic)]
+        participants: Vec<AccountId>,
+        #[ink(topic)]
+        task: String,
+        /// Skills the task requires.
+        required_skills: Vec<String>,
+        /// Whether all participants have the required skills. `false` if too few
+        /// members qualified for any task and selection fell back to all members.
+        skills_matched: bool,
+    }
+
+    #[ink(event)]
+    pub struct GroupTimedOut {
+        era: u32,
+        group: u32,
+        #[ink(topic)]
+        task: String,
+    }
+
+    #[ink(event)]
+    pub struct AcceptanceWindowSet {
+        blocks: u32,
+    }
+
+    #[ink(event)]
+    pub struct AssignmentAccepted {
+        era: u32,
+        group: u32,
+        #[ink(topic)]
+        member: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AssignmentDeclined {
+        era: u32,
+        group: u32,
+        #[ink(topic)]
+        member: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ParticipantReplaced {
+        era: u32,
+        group: u32,
+        #[ink(topic)]
+        replaced: AccountId,
+        /// Member taking over one of the slots, `None` if no member was left to draw.
+        replacement: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct GroupActivated {
+        era: u32,
+        group: u32,
+    }
+
+    #[ink(event)]
+    pub struct GroupsPerEraSet {
+        groups: u32,
+    }
+
+    #[ink(event)]
+    pub struct KeeperRewardSet {
+        tip: Balance,
+        fee_bps: u32,
+    }
+
+    #[ink(event)]
+    pub struct KeeperPoolFunded {
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct KeeperRewarded {
+        #[ink(topic)]
+        keeper: AccountId,
+        era: u32,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct GroupTimeoutSet {
+        blocks: u32,
+    }
+
+    #[ink(event)]
+    pub struct BadgeMinted {
+        #[ink(topic)]
+        member: AccountId,
+        id: Id,
+        #[ink(topic)]
+        task: String,
+        era: u32,
+    }
+
+    #[ink(storage)]
+    pub struct Polkapobal {
+        owner: AccountId,
+        // Account allowed to pause and unpause besides the owner
+        pauser: AccountId,
+        // Emergency stop for every pausable operation
+        paused: bool,
+        paused_categories: Mapping<PauseCategory, ()>,
+        members: Vec<AccountId>,
+        is_member: Mapping<AccountId, ()>,
+        // member -> declared skills
+        member_skills: Mapping<AccountId, Vec<String>>,
+        // member -> block from which the member can be selected again
+        unavailable_until: Mapping<AccountId, u32>,
+        // member -> last era the member was selected in
+        last_selected_era: Mapping<AccountId, u32>,
+        // member -> number of eras the member was selected in
+        times_selected: Mapping<AccountId, u32>,
+        // member -> era the member was selected in before the last selection
+        previous_selected_era: Mapping<AccountId, u32>,
+        selection_strategy: SelectionStrategy,
+        // Eras a selected member has to wait before being eligible again
+        selection_cooldown: u32,
+        // Number of eras started so far
+        era_count: u32,
+        // Distinct participants of the most recent eras, oldest first
+        group_history: Vec<Vec<AccountId>>,
+        // Number of eras kept in `group_history`
+        group_history_limit: u32,
+        tasks: Vec<String>,
+        // task -> (is_complete, funds)
+        task_info: Mapping<String, (bool, Balance)>,
+        // task -> tags, each of which is one of `categories`
+        task_tags: Mapping<String, Vec<String>>,
+        // task -> skills participants must have
+        task_skills: Mapping<String, Vec<String>>,
+        // Accounts allowed to manage categories and tags besides the owner
+        curators: Mapping<AccountId, ()>,
+        categories: Vec<String>,
+        // Categories `randomly_select_task` draws from, empty for all tasks
+        era_categories: Vec<String>,
+        // task -> recurrence, `Recurrence::Once` if not set
+        task_recurrence: Mapping<String, Recurrence>,
+        // (task, index) -> completed run of the task, numbered from 0
+        task_history: Mapping<(String, u32), Occurrence>,
+        // task -> number of completed runs recorded in `task_history`
+        task_occurrences: Mapping<String, u32>,
+        // reopened task -> block from which it can be selected again
+        task_reopens_at: Mapping<String, u32>,
+        // task -> (available_from, expires_at) block numbers
+        task_windows: Mapping<String, TaskWindow>,
+        // task -> (target, cap) of its native bounty
+        funding_goals: Mapping<String, FundingGoal>,
+        // Whether tasks below their funding target are left out of selection
+        funding_goals_required: bool,
+        // Whether a matching round is open
+        matching_round_open: bool,
+        // Funds the open matching round distributes when it closes
+        matching_pool: Balance,
+        // Id of the open or last matching round
+        matching_rounds: u32,
+        // Tasks funded during the open matching round
+        round_tasks: Vec<String>,
+        // (task, donor) -> matching round and the donor's native donations to the
+        // task during it. Entries of earlier rounds are ignored.
+        round_contributions: Mapping<(String, AccountId), RoundContribution>,
+        // task -> sum of the square roots of its contributions and sum of its
+        // contributions during the open matching round
+        round_scores: Mapping<String, (Balance, Balance)>,
+        // Id of the next funding round. A task collects donations in a round until it
+        // is paid out or removed, and funding it afterwards starts a new round.
+        funding_rounds: u32,
+        // task -> funding round the task collects donations in
+        task_funding_rounds: Mapping<String, u32>,
+        // task -> native donations in the task's funding round
+        task_donated: Mapping<String, Balance>,
+        // (funding round, donor) -> native donations of the donor in the round
+        task_donations: Mapping<(u32, AccountId), Balance>,
+        // (funding round, donor) -> pledged part of the donor's donations in the round
+        pledges: Mapping<(u32, AccountId), Pledge>,
+        // Funding rounds of expired tasks, whose donors can claim their donations back
+        refundable_rounds: Mapping<u32, ()>,
+        // participant -> native shares whose transfer failed, claimable later
+        pending_payouts: Mapping<AccountId, Balance>,
+        // (participant, token) -> token shares whose transfer failed, claimable later
+        pending_token_payouts: Mapping<(AccountId, AccountId), Balance>,
+        // Index into `tasks` where the next expiry sweep starts
+        expiry_cursor: u32,
+        // task -> members who voted for the task
+        task_voters: Mapping<String, Vec<AccountId>>,
+        // member -> tasks the member voted for
+        member_votes: Mapping<AccountId, Vec<String>>,
+        // Whether new tasks have to be approved before they can be selected
+        task_review: bool,
+        // Member approvals that approve a proposed task, 0 if only curators can
+        approval_threshold: u32,
+        // proposed task -> proposer and members who approved it
+        proposed_tasks: Mapping<String, Proposal>,
+        // task -> era count when the task was added or last selected
+        task_waiting_since: Mapping<String, u32>,
+        task_weighting: TaskWeighting,
+        // Maximum weight of a single task, so no task can monopolize the draw
+        task_weight_cap: Option<u128>,
+        unclaimed_funds: Balance,
+        // PSP22 token contracts that can be used to fund tasks
+        accepted_tokens: Mapping<AccountId, ()>,
+        // task -> [(token, funds)]
+        task_token_funds: Mapping<String, TokenFunds>,
+        // (funding round, donor, token) -> token donations of the donor in the round
+        task_token_donations: Mapping<TokenDonationKey, Balance>,
+        // token -> funds of removed tasks
+        unclaimed_token_funds: Mapping<AccountId, Balance>,
+        start_block: u32,
+        // How many blocks until next selection
+        next_selection: u32,
+        // Last selection block number
+        last_selection: u32,
+        schedule: Schedule,
+        // Last selection timestamp in milliseconds
+        last_selection_time: u64,
+        // Groups of the current era, each with its own task
+        active_groups: Vec<Group>,
+        // Maximum number of groups started per era
+        groups_per_era: u32,
+        // Blocks after the era start in which participants accept or decline their
+        // assignment, 0 to consider every selected member willing
+        acceptance_window: u32,
+        // Members who declined or did not answer an assignment in the current era
+        declined: Vec<AccountId>,
+        // Blocks after the era start from which unfinished groups time out, 0 to wait
+        // for every group to complete
+        group_timeout: u32,
+        // Funds tipping whoever starts an era
+        keeper_pool: Balance,
+        // Tip paid from `keeper_pool` for starting an era
+        keeper_tip: Balance,
+        // Share of each paid out bounty, in basis points, added to `keeper_pool`
+        keeper_fee_bps: u32,
+        // (era, participant) -> completion proof
+        proofs: Mapping<(u32, AccountId), Proof>,
+        // task -> `ProofVerifier` contract checking the task's proofs
+        task_verifiers: Mapping<String, AccountId>,
+        // Oracles whose signed attestations approve completion proofs
+        oracles: Mapping<OracleKey, ()>,
+        // task -> oracles attesting the task's proofs, none are accepted if not set
+        task_oracles: Mapping<String, Vec<OracleKey>>,
+        // participant -> nonce the next attestation for the participant must be signed with
+        attestation_nonces: Mapping<AccountId, u64>,
+        // task -> proofs needed for completion, `ProofRule::Optional` if not set
+        task_proof_rules: Mapping<String, ProofRule>,
+        // badge id -> (owner, badge)
+        badges: Mapping<u64, (AccountId, Badge)>,
+        // member -> number of badges owned
+        badge_balances: Mapping<AccountId, u32>,
+        // Number of badges minted, also used as the next badge id
+        badge_supply: u64,
+        // Whether a batched `clear_members_batch` sweep is in progress
+        clearing_members: bool,
+        // Whether a batched `clear_tasks_batch` sweep is in progress
+        clearing_tasks: bool,
+    }
+
+    impl Polkapobal {
+        #[ink(constructor)]
+        pub fn new(selection_era: u32) -> Self {
+            let current_block = Self::env().block_number();
+            Polkapobal {
+                owner: Self::env().caller(),
+                pauser: Self::env().caller(),
+                paused: false,
+                paused_categories: Mapping::default(),
+                members: Vec::new(),
+                is_member: Mapping::default(),
+                member_skills: Mapping::default(),
+                unavailable_until: Mapping::default(),
+                last_selected_era: Mapping::default(),
+                times_selected: Mapping::default(),
+                previous_selected_era: Mapping::default(),
+                selection_strategy: SelectionStrategy::Random,
+                selection_cooldown: 0,
+                era_count: 0,
+                group_history: Vec::new(),
+                group_history_limit: DEFAULT_GROUP_HISTORY_LIMIT,
+                tasks: Vec::new(),
+                task_info: Mapping::default(),
+                task_tags: Mapping::default(),
+                task_skills: Mapping::default(),
+                curators: Mapping::default(),
+                categories: Vec::new(),
+                era_categories: Vec::new(),
+                task_recurrence: Mapping::default(),
+                task_history: Mapping::default(),
+                task_occurrences: Mapping::default(),
+                task_reopens_at: Mapping::default(),
+                task_windows: Mapping::default(),
+                funding_goals: Mapping::default(),
+                funding_goals_required: false,
+                matching_round_open: false,
+                matching_pool: 0,
+                matching_rounds: 0,
+                round_tasks: Vec::new(),
+                round_contributions: Mapping::default(),
+                round_scores: Mapping::default(),
+                funding_rounds: 0,
+                task_funding_rounds: Mapping::default(),
+                task_donated: Mapping::default(),
+                task_donations: Mapping::default(),
+                pledges: Mapping::default(),
+                refundable_rounds: Mapping::default(),
+                pending_payouts: Mapping::default(),
+                pending_token_payouts: Mapping::default(),
+                expiry_cursor: 0,
+                task_voters: Mapping::default(),
+                member_votes: Mapping::default(),
+                task_review: false,
+                approval_threshold: 0,
+                proposed_tasks: Mapping::default(),
+                task_waiting_since: Mapping::default(),
+                task_weighting: TaskWeighting::Uniform,
+                task_weight_cap: None,
+                unclaimed_funds: 0,
+                accepted_tokens: Mapping::default(),
+                task_token_funds: Mapping::default(),
+                task_token_donations: Mapping::default(),
+                unclaimed_token_funds: Mapping::default(),
+                start_block: current_block,
+                next_selection: selection_era,
+                last_selection: current_block,
+                schedule: Schedule::Blocks,
+                last_selection_time: Self::env().block_timestamp(),
+                active_groups: Vec::new(),
+                groups_per_era: 1,
+                acceptance_window: 0,
+                declined: Vec::new(),
+                group_timeout: 0,
+                keeper_pool: 0,
+                keeper_tip: 0,
+                keeper_fee_bps: 0,
+                proofs: Mapping::default(),
+                task_verifiers: Mapping::default(),
+                oracles: Mapping::default(),
+                task_oracles: Mapping::default(),
+                attestation_nonces: Mapping::default(),
+                task_proof_rules: Mapping::default(),
+                badges: Mapping::default(),
+                badge_balances: Mapping::default(),
+                badge_supply: 0,
+                clearing_members: false,
+                clearing_tasks: false,
+            }
+        }
+
+        /// Creates a contract whose eras follow wall-clock time instead of block numbers,
+        /// see `Schedule::Timestamp`.
+        #[ink(constructor)]
+        pub fn new_with_timestamp_schedule(period: u64, offset: u64) -> Self {
+            assert!(period > 0, "Schedule period must be positive");
+            assert!(offset < period, "Schedule offset must be below the period");
+
+            // The era length in blocks does not apply to timestamp schedules
+            let mut contract = Self::new(0);
+            contract.schedule = Schedule::Timestamp { period, offset };
+            contract
+        }
 
         #[ink(message)]
-        pub fn remove_task(&mut self, task: String) {
+        pub fn get_schedule(&self) -> Schedule {
+            self.schedule
+        }
+
+        /// Expected start of the next era as `(block number, timestamp in ms)`, using
+        /// `EXPECTED_BLOCK_TIME_MS` to convert to the unit the schedule is not based on.
+        /// Past starts are reported as the current block and timestamp.
+        #[ink(message)]
+        pub fn get_next_era_start(&self) -> (u32, u64) {
+            let block = self.env().block_number();
+            let now = self.env().block_timestamp();
+
+            match self.schedule {
+                Schedule::Blocks => {
+                    let start = self.last_selection.saturating_add(self.next_selection);
+                    let blocks_left = start.saturating_sub(block);
+                    (
+                        start.max(block),
+                        now + blocks_left as u64 * EXPECTED_BLOCK_TIME_MS,
+                    )
+                }
+                Schedule::Timestamp { .. } => {
+                    let start = self.next_era_time();
+                    let blocks_left = start.saturating_sub(now).div_ceil(EXPECTED_BLOCK_TIME_MS);
+                    (
+                        block.saturating_add(blocks_left.try_into().unwrap_or(u32::MAX)),
+                        start.max(now),
+                    )
+                }
+            }
+        }
+
+        /// Sets the era length in blocks. Only applies to `Schedule::Blocks`.
+        #[ink(message)]
+        pub fn set_selection_era(&mut self, selection_era: u32) {
+            self.ensure_owner();
+            assert!(
+                self.schedule == Schedule::Blocks,
+                "Eras follow a timestamp schedule"
+            );
+
+            self.next_selection = selection_era;
+
+            self.env().emit_event(SelectionEraChanged {
+                new_era: selection_era,
+            })
+        }
+
+        #[ink(message)]
+        pub fn set_selection_strategy(&mut self, strategy: SelectionStrategy) {
+            self.ensure_owner();
+
+            self.selection_strategy = strategy;
+
+            self.env().emit_event(SelectionStrategySet { strategy });
+        }
+
+        /// A member selected in era N can only be selected again from era N + `eras`.
+        #[ink(message)]
+        pub fn set_selection_cooldown(&mut self, eras: u32) {
+            self.ensure_owner();
+
+            self.selection_cooldown = eras;
+
+            self.env().emit_event(SelectionCooldownSet { eras });
+        }
+
+        /// Sets how many past groups are kept to count pairings, dropping the oldest
+        /// groups beyond the new limit.
+        #[ink(message)]
+        pub fn set_group_history_limit(&mut self, eras: u32) {
+            self.ensure_owner();
+
+            self.group_history_limit = eras;
+            self.prune_group_history();
+
+            self.env().emit_event(GroupHistoryLimitSet { eras });
+        }
+
+        /// Number of recorded groups both `a` and `b` were part of.
+        #[ink(message)]
+        pub fn get_pair_count(&self, a: AccountId, b: AccountId) -> u32 {
+            self.group_history
+                .iter()
+                .filter(|group| group.contains(&a) && group.contains(&b))
+                .count() as u32
+        }
+
+        /// Sets how tasks are weighed when drawing the task of an era. `cap` limits the
+        /// weight of a single task, in balance units for `TaskWeighting::Bounty` and in
+        /// eras for `TaskWeighting::Age`.
+        #[ink(message)]
+        pub fn set_task_weighting(&mut self, weighting: TaskWeighting, cap: Option<u128>) {
+            self.ensure_owner();
+
+            self.task_weighting = weighting;
+            self.task_weight_cap = cap;
+
+            self.env().emit_event(TaskWeightingSet { weighting, cap });
+        }
+
+        /// Sets how many blocks selected members have to accept their assignment. Members
+        /// declining are replaced right away, members not answering once the window
+        /// closes. 0 skips the acceptance step.
+        #[ink(message)]
+        pub fn set_acceptance_window(&mut self, blocks: u32) {
             self.ensure_owner();
 
-            // Ensure that the task does exists
-            assert!(self.task_info.contains(&task), "Task does not exist");
+            self.acceptance_window = blocks;
+
+            self.env().emit_event(AcceptanceWindowSet { blocks });
+        }
+
+        #[ink(message)]
+        pub fn accept_assignment(&mut self, group: u32) {
+            let caller = self.env().caller();
+
+            self.ensure_not_paused(PauseCategory::Eras);
+            self.ensure_acceptance_window_open();
+
+            let active = self
+                .active_groups
+                .get_mut(group as usize)
+                .expect("Group does not exist");
+            let index = active
+                .pending
+                .iter()
+                .position(|member| *member == caller)
+                .expect("No pending assignment");
+            active.pending.swap_remove(index);
+            let activated = active.pending.is_empty();
+
+            self.env().emit_event(AssignmentAccepted {
+                era: self.last_selection,
+                group,
+                member: caller,
+            });
+            if activated {
+                self.env().emit_event(GroupActivated {
+                    era: self.last_selection,
+                    group,
+                });
+            }
+        }
+
+        /// Declines the caller's assignment, handing its slots to fresh members who
+        /// have to accept in turn.
+        #[ink(message)]
+        pub fn decline_assignment(&mut self, group: u32) {
+            let caller = self.env().caller();
+
+            self.ensure_not_paused(PauseCategory::Eras);
+            self.ensure_acceptance_window_open();
+
+            let active = self
+                .active_groups
+                .get(group as usize)
+                .expect("Group does not exist");
+            assert!(active.pending.contains(&caller), "No pending assignment");
+
+            self.env().emit_event(AssignmentDeclined {
+                era: self.last_selection,
+                group,
+                member: caller,
+            });
+
+            self.undo_selection(caller);
+            self.replace_participant(group as usize, caller, true);
+            if self.active_groups[group as usize].pending.is_empty() {
+                self.env().emit_event(GroupActivated {
+                    era: self.last_selection,
+                    group,
+                });
+            }
+        }
+
+        /// Replaces the members who did not answer their assignment once the acceptance
+        /// window closed, activating every group. Callable by anyone.
+        #[ink(message)]
+        pub fn close_acceptance(&mut self) {
+            self.ensure_not_paused(PauseCategory::Eras);
+            assert!(
+                !self.is_acceptance_window_open(),
+                "Acceptance window still open"
+            );
+
+            let mut settled = false;
+            for group in 0..self.active_groups.len() {
+                settled |= self.settle_group(group);
+            }
+            // Only the call that activates the groups is tipped
+            if settled {
+                self.reward_keeper();
+            }
+        }
+
+        #[ink(message)]
+        pub fn is_group_active(&self, group: u32) -> bool {
+            self.active_groups
+                .get(group as usize)
+                .is_some_and(|active| active.pending.is_empty())
+        }
+
+        /// Sets how many groups, each with its own task, are started per era. Groups
+        /// after the first are only formed if they can be fully staffed.
+        #[ink(message)]
+        pub fn set_groups_per_era(&mut self, groups: u32) {
+            self.ensure_owner();
+            assert!(groups > 0, "Must start at least one group");
+
+            self.groups_per_era = groups;
+
+            self.env().emit_event(GroupsPerEraSet { groups });
+        }
+
+        /// Sets the tip paid from the keeper pool to whoever starts an era or closes the
+        /// acceptance window, once per call, and the share of paid out bounties, in basis
+        /// points, that refills the pool.
+        #[ink(message)]
+        pub fn set_keeper_reward(&mut self, tip: Balance, fee_bps: u32) {
+            self.ensure_owner();
+
+            assert!(tip <= MAX_KEEPER_TIP, "Keeper tip too high");
+            assert!(fee_bps <= MAX_KEEPER_FEE_BPS, "Keeper fee too high");
+
+            self.keeper_tip = tip;
+            self.keeper_fee_bps = fee_bps;
+
+            self.env().emit_event(KeeperRewardSet { tip, fee_bps });
+        }
+
+        #[ink(message, payable)]
+        pub fn fund_keeper_pool(&mut self) {
+            let caller = self.env().caller();
+            let transferred = self.env().transferred_value();
+
+            self.ensure_not_paused(PauseCategory::Funding);
+
+            self.keeper_pool = self
+                .keeper_pool
+                .checked_add(transferred)
+                .expect("Balance overflow");
+
+            self.env().emit_event(KeeperPoolFunded {
+                donor: caller,
+                amount: transferred,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_keeper_pool(&self) -> Balance {
+            self.keeper_pool
+        }
+
+        /// Lets the next era start `blocks` after the current one started even if some
+        /// groups did not complete their task. 0 waits for every group. The timeout is
+        /// measured from the start of the era, so it includes any acceptance window.
+        #[ink(message)]
+        pub fn set_group_timeout(&mut self, blocks: u32) {
+            self.ensure_owner();
+
+            self.group_timeout = blocks;
+
+            self.env().emit_event(GroupTimeoutSet { blocks });
+        }
+
+        #[ink(message)]
+        pub fn get_active_groups(&self) -> Vec<Group> {
+            self.active_groups.clone()
+        }
+
+        #[ink(message)]
+        pub fn set_pauser(&mut self, pauser: AccountId) {
+            self.ensure_owner();
+
+            self.pauser = pauser;
+        }
+
+        #[ink(message)]
+        pub fn pause(&mut self) {
+            self.ensure_owner_or_pauser();
+
+            self.paused = true;
+
+            self.env().emit_event(Paused { category: None });
+        }
+
+        #[ink(message)]
+        pub fn unpause(&mut self) {
+            self.ensure_owner_or_pauser();
+
+            self.paused = false;
+
+            self.env().emit_event(Unpaused { category: None });
+        }
+
+        #[ink(message)]
+        pub fn pause_category(&mut self, category: PauseCategory) {
+            self.ensure_owner_or_pauser();
+
+            self.paused_categories.insert(category, &());
+
+            self.env().emit_event(Paused {
+                category: Some(category),
+            });
+        }
+
+        #[ink(message)]
+        pub fn unpause_category(&mut self, category: PauseCategory) {
+            self.ensure_owner_or_pauser();
+
+            self.paused_categories.remove(category);
+
+            self.env().emit_event(Unpaused {
+                category: Some(category),
+            });
+        }
+
+        #[ink(message)]
+        pub fn is_paused(&self, category: PauseCategory) -> bool {
+            self.paused || self.paused_categories.contains(category)
+        }
+
+        #[ink(message)]
+        pub fn register_member(&mut self) {
+            let caller = self.env().caller();
+
+            self.ensure_not_paused(PauseCategory::Membership);
+            self.ensure_not_clearing_members();
+
+            // Ensure that the member does not exist
+            assert!(!self.is_member.contains(caller), "Member already exists");
+
+            self.is_member.insert(caller, &());
+            self.members.push(caller);
+
+            self.env().emit_event(MemberRegistered { member: caller });
+        }
+
+        #[ink(message)]
+        pub fn deregister_member(&mut self) {
+            let caller = self.env().caller();
+
+            // Ensure that the member exists
+            self.ensure_member();
+            self.ensure_not_clearing_members();
+
+            // Search for index of member
+            let index = self
+                .members
+                .iter()
+                .position(|x| *x == caller)
+                .expect("Member existence verified before calling");
+            self.members.swap_remove(index);
+            self.release_member(caller);
+
+            // Hand the member's slots in unfinished groups to replacements
+            for group in 0..self.active_groups.len() {
+                let active = &self.active_groups[group];
+                if active.is_complete || !active.participants.contains(&caller) {
+                    continue;
+                }
+
+                let was_pending = !active.pending.is_empty();
+                let needs_acceptance = was_pending && self.is_acceptance_window_open();
+                self.replace_participant(group, caller, needs_acceptance);
+                if was_pending && self.active_groups[group].pending.is_empty() {
+                    self.env().emit_event(GroupActivated {
+                        era: self.last_selection,
+                        group: group as u32,
+                    });
+                }
+            }
+
+            self.env().emit_event(MemberDeregistered { member: caller });
+        }
+
+        /// Excludes the caller from era selection until `until_block`, without
+        /// deregistering.
+        #[ink(message)]
+        pub fn set_unavailable(&mut self, until_block: u32) {
+            let caller = self.env().caller();
+
+            self.ensure_member();
+            assert!(
+                until_block > self.env().block_number(),
+                "Block must be in the future"
+            );
+
+            self.unavailable_until.insert(caller, &until_block);
+
+            self.env().emit_event(MemberUnavailable {
+                member: caller,
+                until_block,
+            });
+        }
+
+        #[ink(message)]
+        pub fn set_available(&mut self) {
+            let caller = self.env().caller();
+
+            self.ensure_member();
+
+            self.unavailable_until.remove(caller);
+
+            self.env().emit_event(MemberAvailable { member: caller });
+        }
+
+        /// Members that can currently be selected for the next era.
+        #[ink(message)]
+        pub fn get_eligible_members(&self) -> Vec<AccountId> {
+            self.eligible_members(self.era_count.saturating_add(1))
+        }
+
+        #[ink(message)]
+        pub fn set_skills(&mut self, skills: Vec<String>) {
+            let caller = self.env().caller();
+
+            self.ensure_member();
+
+            let skills = Self::normalize_skills(skills);
+            self.member_skills.insert(caller, &skills);
+
+            self.env().emit_event(MemberSkillsSet {
+                member: caller,
+                skills,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_skills(&self, member: AccountId) -> Vec<String> {
+            self.member_skills.get(member).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn clear_members(&mut self) {
+            self.ensure_owner();
+
+            self.sweep_members(self.members.len());
+        }
+
+        /// Removes up to `limit` members, returning how many are left. `MembersCleared`
+        /// is emitted once the sweep finishes; member changes are blocked until then.
+        /// Groups of the current era have to complete before members can be cleared.
+        #[ink(message)]
+        pub fn clear_members_batch(&mut self, limit: u32) -> u32 {
+            self.ensure_owner();
+
+            self.sweep_members(limit as usize) as u32
+        }
+
+        #[ink(message)]
+        pub fn add_task(&mut self, task: String) {
+            self.ensure_not_paused(PauseCategory::Membership);
+            self.ensure_member();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does not exist
+            assert!(!self.task_info.contains(&task), "Task already exists");
+
+            self.task_info.insert(&task, &(false, 0));
+            self.task_waiting_since.insert(&task, &self.era_count);
+            self.tasks.push(task.clone());
+
+            if self.task_review {
+                let caller = self.env().caller();
+                self.proposed_tasks
+                    .insert(&task, &(caller, Vec::<AccountId>::new()));
+                self.env().emit_event(TaskProposed { task });
+            } else {
+                self.env().emit_event(TaskAdded { task });
+            }
+        }
+
+        /// When enabled, new tasks are proposed and only become selectable once a
+        /// curator or `approval_threshold` members approve them.
+        #[ink(message)]
+        pub fn set_task_review(&mut self, enabled: bool, approval_threshold: u32) {
+            self.ensure_owner();
+
+            self.task_review = enabled;
+            self.approval_threshold = approval_threshold;
+
+            self.env().emit_event(TaskReviewSet {
+                enabled,
+                approval_threshold,
+            });
+        }
+
+        /// Approves a proposed task if the caller is a curator, otherwise counts the
+        /// caller's approval towards the member threshold. Proposers cannot count
+        /// towards the threshold of their own task.
+        #[ink(message)]
+        pub fn approve_task(&mut self, task: String) {
+            let caller = self.env().caller();
+
+            let (proposer, mut approvals) =
+                self.proposed_tasks.get(&task).expect("Task not proposed");
+
+            if caller == self.owner || self.curators.contains(caller) {
+                self.proposed_tasks.remove(&task);
+                self.env().emit_event(TaskApproved { task });
+                return;
+            }
+
+            self.ensure_member();
+            assert!(
+                self.approval_threshold > 0,
+                "Only owner or curator can call"
+            );
+            assert!(caller != proposer, "Cannot approve own task");
+            assert!(!approvals.contains(&caller), "Already approved task");
+
+            approvals.push(caller);
+            if approvals.len() as u32 >= self.approval_threshold {
+                self.proposed_tasks.remove(&task);
+                self.env().emit_event(TaskApproved { task });
+            } else {
+                self.proposed_tasks.insert(&task, &(proposer, approvals));
+            }
+        }
+
+        /// Rejects and removes a proposed task. Proposed tasks cannot be funded, so
+        /// nothing has to be refunded.
+        #[ink(message)]
+        pub fn reject_task(&mut self, task: String, reason: Hash) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            assert!(self.proposed_tasks.contains(&task), "Task not proposed");
+
+            self.delete_task(&task);
+
+            self.env().emit_event(TaskRejected { task, reason });
+        }
+
+        #[ink(message)]
+        pub fn is_task_proposed(&self, task: String) -> bool {
+            self.proposed_tasks.contains(&task)
+        }
+
+        #[ink(message)]
+        pub fn add_tagged_task(&mut self, task: String, tags: Vec<String>) {
+            self.add_task(task.clone());
+            self.store_task_tags(task, tags);
+        }
+
+        #[ink(message)]
+        pub fn set_task_recurrence(&mut self, task: String, recurrence: Recurrence) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            self.task_recurrence.insert(&task, &recurrence);
+
+            self.env()
+                .emit_event(TaskRecurrenceSet { task, recurrence });
+        }
+
+        /// Limits selection of `task` to blocks in `[available_from, expires_at)`. Once
+        /// expired, the task can be swept and its native funds refunded to its donors.
+        #[ink(message)]
+        pub fn set_task_window(
+            &mut self,
+            task: String,
+            available_from: Option<u32>,
+            expires_at: Option<u32>,
+        ) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            if let (Some(from), Some(until)) = (available_from, expires_at) {
+                assert!(from < until, "Task window is empty");
+            }
+
+            if available_from.is_none() && expires_at.is_none() {
+                self.task_windows.remove(&task);
+            } else {
+                self.task_windows
+                    .insert(&task, &(available_from, expires_at));
+            }
+
+            self.env().emit_event(TaskWindowSet {
+                task,
+                available_from,
+                expires_at,
+            });
+        }
+
+        /// Sets the native bounty `task` aims for, and the cap above which further
+        /// contributions are rejected.
+        #[ink(message)]
+        pub fn set_funding_goal(
+            &mut self,
+            task: String,
+            target: Option<Balance>,
+            cap: Option<Balance>,
+        ) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            if let (Some(target), Some(cap)) = (target, cap) {
+                assert!(target <= cap, "Funding target above cap");
+            }
+
+            if target.is_none() && cap.is_none() {
+                self.funding_goals.remove(&task);
+            } else {
+                self.funding_goals.insert(&task, &(target, cap));
+            }
+
+            self.env().emit_event(FundingGoalSet { task, target, cap });
+        }
+
+        #[ink(message)]
+        pub fn get_funding_goal(&self, task: String) -> FundingGoal {
+            self.funding_goals.get(&task).unwrap_or((None, None))
+        }
+
+        /// Opens a matching round with a pool of `from_unclaimed` unclaimed funds plus
+        /// the transferred value. Donations made with `fund_task` while the round is
+        /// open are matched from the pool when it closes.
+        #[ink(message, payable)]
+        pub fn open_matching_round(&mut self, from_unclaimed: Balance) {
+            self.ensure_owner();
+            self.ensure_not_paused(PauseCategory::Funding);
+
+            assert!(!self.matching_round_open, "Matching round already open");
+            assert!(
+                from_unclaimed <= self.unclaimed_funds,
+                "Not enough unclaimed funds"
+            );
+
+            self.unclaimed_funds -= from_unclaimed;
+            let pool = from_unclaimed
+                .checked_add(self.env().transferred_value())
+                .expect("Balance overflow");
+            self.matching_pool = pool;
+            self.matching_round_open = true;
+            self.matching_rounds = self.matching_rounds.checked_add(1).expect("Round overflow");
+
+            self.env().emit_event(MatchingRoundOpened { pool });
+        }
+
+        #[ink(message, payable)]
+        pub fn fund_matching_pool(&mut self) {
+            let caller = self.env().caller();
+            let transferred = self.env().transferred_value();
+
+            self.ensure_not_paused(PauseCategory::Funding);
+
+            assert!(self.matching_round_open, "No matching round open");
+
+            self.matching_pool = self
+                .matching_pool
+                .checked_add(transferred)
+                .expect("Balance overflow");
+
+            self.env().emit_event(MatchingPoolFunded {
+                donor: caller,
+                amount: transferred,
+            });
+        }
+
+        /// Closes the matching round, adding the pool to the bounties of the tasks
+        /// funded during the round with quadratic funding: a task's share is
+        /// proportional to the square of the sum of the square roots of its
+        /// contributions, minus the contributions themselves. Whatever is not
+        /// allocated goes back to the unclaimed funds.
+        #[ink(message)]
+        pub fn close_matching_round(&mut self) {
+            self.ensure_owner();
+
+            assert!(self.matching_round_open, "No matching round open");
+
+            let mut scores: Vec<(String, Balance)> = Vec::new();
+            for task in core::mem::take(&mut self.round_tasks) {
+                let (roots, sum) = self.round_scores.take(&task).unwrap_or((0, 0));
+                let open = self.task_info.get(&task).is_some_and(|info| !info.0);
+                if open {
+                    scores.push((task, Self::quadratic_match(roots, sum)));
+                }
+            }
+            let total = scores
+                .iter()
+                .try_fold(0u128, |total, (_, score)| total.checked_add(*score))
+                .expect("Balance overflow");
+
+            let pool = core::mem::take(&mut self.matching_pool);
+            let mut allocated: Balance = 0;
+            let mut allocations: Vec<(String, Balance)> = Vec::new();
+            for (task, score) in scores {
+                // `pool * score` can exceed 128 bits, and the share never exceeds `pool`
+                let share = (U256::from(pool) * U256::from(score))
+                    .checked_div(U256::from(total))
+                    .map_or(0, |share| share.as_u128());
+                let mut task_info = self
+                    .task_info
+                    .get(&task)
+                    .expect("Task existence verified before calling");
+                // Respect the funding cap of the task
+                let room = match self.funding_goals.get(&task).and_then(|(_, cap)| cap) {
+                    Some(cap) => cap.saturating_sub(task_info.1),
+                    None => Balance::MAX - task_info.1,
+                };
+                let amount = share.min(room);
+                if amount == 0 {
+                    continue;
+                }
+
+                task_info.1 += amount;
+                self.task_info.insert(&task, &task_info);
+                allocated += amount;
+                allocations.push((task, amount));
+            }
+
+            let unallocated = pool - allocated;
+            self.unclaimed_funds = self
+                .unclaimed_funds
+                .checked_add(unallocated)
+                .expect("Balance overflow");
+            self.matching_round_open = false;
+
+            self.env().emit_event(MatchingRoundClosed {
+                allocations,
+                unallocated,
+            });
+        }
+
+        /// Sets whether tasks have to reach their funding target to be selected.
+        #[ink(message)]
+        pub fn set_funding_goals_required(&mut self, required: bool) {
+            self.ensure_owner();
+
+            self.funding_goals_required = required;
+
+            self.env().emit_event(FundingGoalsRequiredSet { required });
+        }
+
+        #[ink(message)]
+        pub fn get_task_window(&self, task: String) -> TaskWindow {
+            self.task_windows.get(&task).unwrap_or((None, None))
+        }
+
+        /// Examines up to `limit` tasks, continuing where the previous sweep stopped, and
+        /// removes the expired ones, returning how many were removed. Donations to removed
+        /// tasks become claimable by their donors with `claim_refund` and
+        /// `claim_token_refund`, given the funding round of the task. Callable by anyone.
+        #[ink(message)]
+        pub fn sweep_expired_tasks(&mut self, limit: u32) -> u32 {
+            self.ensure_not_clearing_tasks();
+
+            // Examine at most `limit` tasks, continuing where the last sweep stopped
+            let mut cursor = self.expiry_cursor as usize;
+            let mut swept: u32 = 0;
+            for _ in 0..(limit as usize).min(self.tasks.len()) {
+                if cursor >= self.tasks.len() {
+                    cursor = 0;
+                }
+                let task = self.tasks[cursor].clone();
+                if !self.is_expired(&task) {
+                    cursor += 1;
+                    continue;
+                }
+
+                let round = self.task_funding_rounds.get(&task);
+                let refunded = self.refund_donations(&task);
+                self.release_task(&task);
+                // The last task moves to `cursor`, so it is examined next
+                self.tasks.swap_remove(cursor);
+                swept += 1;
+
+                self.env().emit_event(TaskExpired {
+                    task,
+                    round,
+                    refunded,
+                });
+            }
+            self.expiry_cursor = cursor as u32;
+
+            swept
+        }
+
+        /// Transfers the caller's native donations in the funding `round` of an expired
+        /// task.
+        #[ink(message)]
+        pub fn claim_refund(&mut self, round: u32) {
+            let caller = self.env().caller();
+
+            assert!(self.refundable_rounds.contains(round), "Nothing to refund");
+            let amount = self.task_donations.take((round, caller)).unwrap_or(0);
+            assert!(amount > 0, "Nothing to refund");
+            self.pledges.remove((round, caller));
+
+            self.env()
+                .transfer(caller, amount)
+                .expect("Transfer failed");
+
+            self.env().emit_event(RefundClaimed {
+                donor: caller,
+                amount,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_refund(&self, round: u32, donor: AccountId) -> Balance {
+            if !self.refundable_rounds.contains(round) {
+                return 0;
+            }
+            self.task_donations.get((round, donor)).unwrap_or(0)
+        }
+
+        /// Transfers the caller's donations of the PSP22 `token` in the funding `round`
+        /// of an expired task.
+        #[ink(message)]
+        pub fn claim_token_refund(&mut self, round: u32, token: AccountId) {
+            let caller = self.env().caller();
+
+            assert!(self.refundable_rounds.contains(round), "Nothing to refund");
+            let amount = self
+                .task_token_donations
+                .take((round, caller, token))
+                .unwrap_or(0);
+            assert!(amount > 0, "Nothing to refund");
+
+            psp22::transfer(token, caller, amount).expect("Token transfer failed");
+
+            self.env().emit_event(TokenRefundClaimed {
+                donor: caller,
+                token,
+                amount,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_token_refund(&self, round: u32, donor: AccountId, token: AccountId) -> Balance {
+            if !self.refundable_rounds.contains(round) {
+                return 0;
+            }
+            self.task_token_donations
+                .get((round, donor, token))
+                .unwrap_or(0)
+        }
+
+        /// Funding round `task` currently collects donations in, if it was funded since
+        /// it was last paid out.
+        #[ink(message)]
+        pub fn get_funding_round(&self, task: String) -> Option<u32> {
+            self.task_funding_rounds.get(&task)
+        }
+
+        /// Transfers the caller's bounty shares whose payout failed.
+        #[ink(message)]
+        pub fn claim_payout(&mut self) {
+            let caller = self.env().caller();
+
+            let amount = self.pending_payouts.take(caller).unwrap_or(0);
+            assert!(amount > 0, "Nothing to claim");
+
+            self.env()
+                .transfer(caller, amount)
+                .expect("Transfer failed");
+
+            self.env().emit_event(PayoutClaimed {
+                participant: caller,
+                amount,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_pending_payout(&self, participant: AccountId) -> Balance {
+            self.pending_payouts.get(participant).unwrap_or(0)
+        }
+
+        /// Transfers the caller's token bounty shares whose payout failed.
+        #[ink(message)]
+        pub fn claim_token_payout(&mut self, token: AccountId) {
+            let caller = self.env().caller();
+
+            let amount = self
+                .pending_token_payouts
+                .take((caller, token))
+                .unwrap_or(0);
+            assert!(amount > 0, "Nothing to claim");
+
+            psp22::transfer(token, caller, amount).expect("Token transfer failed");
+
+            self.env().emit_event(TokenPayoutClaimed {
+                participant: caller,
+                token,
+                amount,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_pending_token_payout(
+            &self,
+            participant: AccountId,
+            token: AccountId,
+        ) -> Balance {
+            self.pending_token_payouts
+                .get((participant, token))
+                .unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn set_proof_rule(&mut self, task: String, rule: ProofRule) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            self.task_proof_rules.insert(&task, &rule);
+
+            self.env().emit_event(ProofRuleSet { task, rule });
+        }
+
+        /// Has the proofs of `task` checked by the `ProofVerifier` contract `verifier`.
+        /// Once the approved proofs satisfy the task's proof rule, the group is
+        /// completed without waiting for the owner.
+        #[ink(message)]
+        pub fn set_task_verifier(&mut self, task: String, verifier: Option<AccountId>) {
+            self.ensure_owner();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            match verifier {
+                Some(verifier) => self.task_verifiers.insert(&task, &verifier),
+                None => {
+                    self.task_verifiers.remove(&task);
+                    None
+                }
+            };
+
+            self.env().emit_event(TaskVerifierSet { task, verifier });
+        }
+
+        #[ink(message)]
+        pub fn add_oracle(&mut self, oracle: OracleKey) {
+            self.ensure_owner();
+
+            self.oracles.insert(oracle, &());
+
+            self.env().emit_event(OracleAdded { oracle });
+        }
+
+        #[ink(message)]
+        pub fn remove_oracle(&mut self, oracle: OracleKey) {
+            self.ensure_owner();
+
+            self.oracles.remove(oracle);
+
+            self.env().emit_event(OracleRemoved { oracle });
+        }
+
+        #[ink(message)]
+        pub fn is_oracle(&self, oracle: OracleKey) -> bool {
+            self.oracles.contains(oracle)
+        }
+
+        /// Lets the registered `oracles` approve the proofs of `task` with
+        /// `submit_attestation`, replacing the ones set before. Attestations are
+        /// refused for tasks without oracles.
+        #[ink(message)]
+        pub fn set_task_oracles(&mut self, task: String, oracles: Vec<OracleKey>) {
+            self.ensure_owner();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+            assert!(oracles.len() <= MAX_TASK_ORACLES, "Too many oracles");
+            for oracle in &oracles {
+                assert!(self.oracles.contains(oracle), "Unknown oracle");
+            }
+
+            if oracles.is_empty() {
+                self.task_oracles.remove(&task);
+            } else {
+                self.task_oracles.insert(&task, &oracles);
+            }
+
+            self.env().emit_event(TaskOraclesSet { task, oracles });
+        }
+
+        #[ink(message)]
+        pub fn get_task_oracles(&self, task: String) -> Vec<OracleKey> {
+            self.task_oracles.get(&task).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_attestation_nonce(&self, participant: AccountId) -> u64 {
+            self.attestation_nonces.get(participant).unwrap_or(0)
+        }
+
+        /// Approves the caller's proof for the current era with a secp256k1 signature
+        /// of the oracle set for the task over the blake2x256 hash of the SCALE encoded
+        /// `(contract, era, task, participant, nonce)`, `nonce` being the participant's
+        /// `get_attestation_nonce`.
+        #[ink(message)]
+        pub fn submit_attestation(&mut self, signature: [u8; 65]) {
+            let caller = self.env().caller();
+
+            let group = self
+                .active_groups
+                .iter()
+                .position(|group| group.participants.contains(&caller))
+                .expect("Caller must be active participant");
+            let task = self.active_groups[group].task.clone();
+            assert!(
+                !self.active_groups[group].is_complete,
+                "Task already completed"
+            );
+            let task_oracles = self
+                .task_oracles
+                .get(&task)
+                .expect("Task takes no attestations");
+
+            let nonce = self.get_attestation_nonce(caller);
+            let message_hash = self.attestation_hash(&task, caller, nonce);
+            let mut oracle: OracleKey = [0; 33];
+            ink::env::ecdsa_recover(&signature, &message_hash, &mut oracle)
+                .expect("Invalid signature");
+            assert!(
+                self.oracles.contains(oracle) && task_oracles.contains(&oracle),
+                "Unknown oracle"
+            );
+
+            self.attestation_nonces.insert(caller, &(nonce + 1));
+
+            // Keep an uploaded proof's content, otherwise the attestation is the proof
+            let key = (self.last_selection, caller);
+            let proof = match self.proofs.get(key) {
+                Some(proof) => proof,
+                None => Proof {
+                    task: task.clone(),
+                    hash: Hash::from(message_hash),
+                    uri: None,
+                    kind: ProofKind::Attestation,
+                    timestamp: self.env().block_timestamp(),
+                    approved: false,
+                },
+            };
+            self.proofs.insert(key, &proof);
+
+            self.env().emit_event(AttestationAccepted {
+                era: self.last_selection,
+                participant: caller,
+                task,
+                oracle,
+                nonce,
+            });
+
+            self.record_verification(group, caller, true);
+        }
+
+        #[ink(message)]
+        pub fn get_task_verifier(&self, task: String) -> Option<AccountId> {
+            self.task_verifiers.get(&task)
+        }
+
+        #[ink(message)]
+        pub fn get_proof(&self, era: u32, participant: AccountId) -> Option<Proof> {
+            self.proofs.get((era, participant))
+        }
+
+        #[ink(message)]
+        pub fn get_task_occurrence_count(&self, task: String) -> u32 {
+            self.task_occurrences.get(&task).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_task_occurrence(&self, task: String, index: u32) -> Option<Occurrence> {
+            if index >= self.task_occurrences.get(&task).unwrap_or(0) {
+                return None;
+            }
+            self.task_history.get((task, index))
+        }
+
+        #[ink(message)]
+        pub fn vote_task(&mut self, task: String) {
+            let caller = self.env().caller();
+
+            self.ensure_member();
+            let mut voters = self.votable_task_voters(&task);
+            assert!(!voters.contains(&caller), "Already voted for task");
+
+            voters.push(caller);
+            self.task_voters.insert(&task, &voters);
+
+            let mut votes = self.member_votes.get(caller).unwrap_or_default();
+            votes.push(task.clone());
+            self.member_votes.insert(caller, &votes);
+
+            self.env().emit_event(TaskVoted {
+                task,
+                member: caller,
+            });
+        }
+
+        #[ink(message)]
+        pub fn unvote_task(&mut self, task: String) {
+            let caller = self.env().caller();
+
+            self.ensure_member();
+            let mut voters = self.votable_task_voters(&task);
+            let index = voters
+                .iter()
+                .position(|x| *x == caller)
+                .expect("Not voted for task");
+
+            voters.swap_remove(index);
+            self.task_voters.insert(&task, &voters);
+
+            let mut votes = self.member_votes.get(caller).unwrap_or_default();
+            votes.retain(|voted| *voted != task);
+            self.member_votes.insert(caller, &votes);
+
+            self.env().emit_event(TaskUnvoted {
+                task,
+                member: caller,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_task_votes(&self, task: String) -> u32 {
+            self.task_voters
+                .get(&task)
+                .map_or(0, |voters| voters.len() as u32)
+        }
+
+        #[ink(message)]
+        pub fn set_task_tags(&mut self, task: String, tags: Vec<String>) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            self.store_task_tags(task, tags);
+        }
+
+        #[ink(message)]
+        pub fn get_task_tags(&self, task: String) -> Vec<String> {
+            self.task_tags.get(&task).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_tasks_by_tag(&self, tag: String) -> Vec<String> {
+            self.tasks
+                .iter()
+                .filter(|task| self.get_task_tags((*task).clone()).contains(&tag))
+                .cloned()
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn set_task_skills(&mut self, task: String, skills: Vec<String>) {
+            self.ensure_owner_or_curator();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            let skills = Self::normalize_skills(skills);
+            self.task_skills.insert(&task, &skills);
+
+            self.env().emit_event(TaskSkillsSet { task, skills });
+        }
+
+        #[ink(message)]
+        pub fn get_task_skills(&self, task: String) -> Vec<String> {
+            self.task_skills.get(&task).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn add_curator(&mut self, curator: AccountId) {
+            self.ensure_owner();
+
+            self.curators.insert(curator, &());
+
+            self.env().emit_event(CuratorAdded { curator });
+        }
+
+        #[ink(message)]
+        pub fn remove_curator(&mut self, curator: AccountId) {
+            self.ensure_owner();
+
+            self.curators.remove(curator);
+
+            self.env().emit_event(CuratorRemoved { curator });
+        }
+
+        #[ink(message)]
+        pub fn add_category(&mut self, category: String) {
+            self.ensure_owner_or_curator();
+
+            // Ensure that the category does not exist
+            assert!(
+                !self.categories.contains(&category),
+                "Category already exists"
+            );
+
+            self.categories.push(category.clone());
+
+            self.env().emit_event(CategoryAdded { category });
+        }
+
+        /// Removes `category` from the registry. Tasks keep it as a tag, but it can no
+        /// longer be assigned to tasks or used as an era filter.
+        #[ink(message)]
+        pub fn remove_category(&mut self, category: String) {
+            self.ensure_owner_or_curator();
+
+            let index = self
+                .categories
+                .iter()
+                .position(|x| *x == category)
+                .expect("Category does not exist");
+            self.categories.swap_remove(index);
+            self.era_categories.retain(|x| *x != category);
+
+            self.env().emit_event(CategoryRemoved { category });
+        }
+
+        #[ink(message)]
+        pub fn get_categories(&self) -> Vec<String> {
+            self.categories.clone()
+        }
+
+        /// Restricts the tasks drawn by following eras to those tagged with at least one
+        /// of `categories`. An empty list draws from all tasks.
+        #[ink(message)]
+        pub fn set_era_categories(&mut self, categories: Vec<String>) {
+            self.ensure_owner_or_curator();
+
+            for category in &categories {
+                self.ensure_category_exists(category);
+            }
+            self.era_categories = categories.clone();
+
+            self.env().emit_event(EraCategoriesSet { categories });
+        }
+
+        #[ink(message)]
+        pub fn remove_task(&mut self, task: String) {
+            self.ensure_owner();
+            self.ensure_not_clearing_tasks();
+
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            self.delete_task(&task);
+
+            self.env().emit_event(TaskRemoved { task });
+        }
+
+        #[ink(message)]
+        pub fn clear_tasks(&mut self) {
+            self.ensure_owner();
+
+            self.sweep_tasks(self.tasks.len());
+        }
+
+        /// Removes up to `limit` tasks, returning how many are left. `TasksCleared` is
+        /// emitted once the sweep finishes; task changes are blocked until then.
+        #[ink(message)]
+        pub fn clear_tasks_batch(&mut self, limit: u32) -> u32 {
+            self.ensure_owner();
+
+            self.sweep_tasks(limit as usize) as u32
+        }
+
+        /// Adds the transferred value to the bounty of `task`. With a `pledge_deadline`,
+        /// the donation can be taken back with `reclaim_pledge` from that block on if
+        /// the task has not been completed by then.
+        #[ink(message, payable)]
+        pub fn fund_task(&mut self, task: String, pledge_deadline: Option<u32>) {
+            let caller = self.env().caller();
+            let transferred = self.env().transferred_value();
+
+            self.ensure_not_paused(PauseCategory::Funding);
+            self.ensure_not_clearing_tasks();
+
+            //Ensure that the task does exist
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            let mut task_info = self
+                .task_info
+                .get(&task)
+                .expect("Task existence verified before calling");
+
+            assert!(!task_info.0, "Task already completed");
+            assert!(!self.is_expired(&task), "Task expired");
+            assert!(!self.proposed_tasks.contains(&task), "Task not approved");
+            assert!(transferred > 0, "Nothing to fund");
+
+            let previous = task_info.1;
+            task_info.1 = task_info
+                .1
+                .checked_add(transferred)
+                .expect("Balance overflow");
+
+            let (target, cap) = self.funding_goals.get(&task).unwrap_or((None, None));
+            if let Some(cap) = cap {
+                assert!(task_info.1 <= cap, "Funding cap exceeded");
+            }
+
+            self.task_info.insert(&task, &task_info);
+
+            let round = self.funding_round(&task);
+            let donated = self
+                .task_donations
+                .get((round, caller))
+                .unwrap_or(0)
+                .checked_add(transferred)
+                .expect("Balance overflow");
+            self.task_donations.insert((round, caller), &donated);
+            let total = self
+                .task_donated
+                .get(&task)
+                .unwrap_or(0)
+                .checked_add(transferred)
+                .expect("Balance overflow");
+            self.task_donated.insert(&task, &total);
+
+            if let Some(deadline) = pledge_deadline {
+                assert!(
+                    deadline > self.env().block_number(),
+                    "Pledge deadline passed"
+                );
+
+                // Later pledges extend the deadline of earlier ones
+                let key = (round, caller);
+                let (pledged, previous_deadline) = self.pledges.get(key).unwrap_or((0, 0));
+                let pledged = pledged.checked_add(transferred).expect("Balance overflow");
+                self.pledges
+                    .insert(key, &(pledged, deadline.max(previous_deadline)));
+            }
+
+            if self.matching_round_open {
+                self.add_round_contribution(&task, caller, transferred);
+            }
+
+            if let Some(target) = target {
+                if previous < target && task_info.1 >= target {
+                    self.env().emit_event(FundingGoalReached {
+                        task: task.clone(),
+                        funds: task_info.1,
+                    });
+                }
+            }
+
+            self.env().emit_event(TaskFunded {
+                task,
+                donor: caller,
+                amount: transferred,
+                pledge_deadline,
+            });
+        }
+
+        /// Pays the caller's pledge to `task` back once its deadline has passed
+        /// without the task being completed. Works while paused, so pledges can't be
+        /// locked in.
+        #[ink(message)]
+        pub fn reclaim_pledge(&mut self, task: String) {
+            let caller = self.env().caller();
+
+            // Pledges end with the funding round on completion, so the task is still open
+            let round = self
+                .task_funding_rounds
+                .get(&task)
+                .expect("No pledge to reclaim");
+            let key = (round, caller);
+            let (amount, deadline) = self.pledges.get(key).expect("No pledge to reclaim");
+            assert!(
+                self.env().block_number() >= deadline,
+                "Pledge deadline not reached"
+            );
+            self.pledges.remove(key);
+
+            let mut task_info = self
+                .task_info
+                .get(&task)
+                .expect("Task existence verified before calling");
+            task_info.1 = task_info.1.saturating_sub(amount);
+            self.task_info.insert(&task, &task_info);
+
+            let donated = self
+                .task_donations
+                .get(key)
+                .unwrap_or(0)
+                .saturating_sub(amount);
+            if donated > 0 {
+                self.task_donations.insert(key, &donated);
+            } else {
+                self.task_donations.remove(key);
+            }
+            let total = self
+                .task_donated
+                .get(&task)
+                .unwrap_or(0)
+                .saturating_sub(amount);
+            self.task_donated.insert(&task, &total);
+            let contribution = self.round_contribution(&task, caller);
+            if contribution > 0 {
+                self.set_round_contribution(&task, caller, contribution.saturating_sub(amount));
+            }
+
+            self.env()
+                .transfer(caller, amount)
+                .expect("Transfer failed");
+
+            self.env().emit_event(PledgeReclaimed {
+                task,
+                donor: caller,
+                amount,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_pledge(&self, task: String, donor: AccountId) -> Option<Pledge> {
+            let round = self.task_funding_rounds.get(&task)?;
+            self.pledges.get((round, donor))
+        }
+
+        #[ink(message)]
+        pub fn add_accepted_token(&mut self, token: AccountId) {
+            self.ensure_owner();
+
+            self.accepted_tokens.insert(token, &());
+
+            self.env().emit_event(AcceptedTokenAdded { token });
+        }
+
+        #[ink(message)]
+        pub fn remove_accepted_token(&mut self, token: AccountId) {
+            self.ensure_owner();
+
+            self.accepted_tokens.remove(token);
+
+            self.env().emit_event(AcceptedTokenRemoved { token });
+        }
+
+        /// Funds `task` with `amount` of the PSP22 `token`.
+        ///
+        /// The caller must have approved this contract to spend `amount` beforehand.
+        #[ink(message)]
+        pub fn fund_task_with_token(&mut self, task: String, token: AccountId, amount: Balance) {
+            let caller = self.env().caller();
+
+            self.ensure_not_paused(PauseCategory::Funding);
+            self.ensure_not_clearing_tasks();
+
+            assert!(self.accepted_tokens.contains(token), "Token not accepted");
+
+            //Ensure that the task does exist
+            assert!(self.task_info.contains(&task), "Task does not exist");
+
+            let task_info = self
+                .task_info
+                .get(&task)
+                .expect("Task existence verified before calling");
+
+            assert!(!task_info.0, "Task already completed");
+            assert!(!self.is_expired(&task), "Task expired");
+            assert!(!self.proposed_tasks.contains(&task), "Task not approved");
+            assert!(amount > 0, "Nothing to fund");
+
+            let mut token_funds = self.task_token_funds.get(&task).unwrap_or_default();
+            match token_funds.iter_mut().find(|(t, _)| *t == token) {
+                Some((_, funds)) => {
+                    *funds = funds.checked_add(amount).expect("Balance overflow");
+                }
+                None => token_funds.push((token, amount)),
+            }
+            self.task_token_funds.insert(&task, &token_funds);
+
+            let key = (self.funding_round(&task), caller, token);
+            let donated = self
+                .task_token_donations
+                .get(key)
+                .unwrap_or(0)
+                .checked_add(amount)
+                .expect("Balance overflow");
+            self.task_token_donations.insert(key, &donated);
+
+            psp22::transfer_from(token, caller, self.env().account_id(), amount)
+                .expect("Token transfer failed");
+
+            self.env().emit_event(TaskFundedWithToken {
+                task,
+                donor: caller,
+                token,
+                amount,
+            });
+        }
+
+        #[ink(message)]
+        pub fn get_task_token_funds(&self, task: String) -> TokenFunds {
+            self.task_token_funds.get(&task).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_unclaimed_token_funds(&self, token: AccountId) -> Balance {
+            self.unclaimed_token_funds.get(token).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn start_new_era(&mut self) {
+            self.ensure_not_paused(PauseCategory::Eras);
+            self.ensure_era_reached();
+            self.ensure_not_clearing_members();
+            self.ensure_not_clearing_tasks();
+            // TODO: simplified logic for MVP
+            self.ensure_active_groups_finished();
+            self.time_out_groups();
+
+            self.ensure_non_empty_members();
+            let eligible = self.eligible_members(self.era_count.saturating_add(1));
+            assert!(!eligible.is_empty(), "No member is available");
+
+            // Each group gets its own task and members not assigned to other groups
+            let mut pool = eligible;
+            let mut groups: Vec<(Group, bool)> = Vec::new();
+            while groups.len() < self.groups_per_era as usize && !pool.is_empty() {
+                if !groups.is_empty() && pool.len() < PARTICIPANTS_PER_ERA {
+                    break;
+                }
+
+                let assigned: Vec<String> =
+                    groups.iter().map(|(group, _)| group.task.clone()).collect();
+                let task = match self.randomly_select_task(&pool, &assigned) {
+                    Some(task) => task,
+                    None => break,
+                };
+
+                let mut candidates = self.qualified_members(&pool, &task);
+                let skills_matched = candidates.len() >= Self::participants_needed(&pool);
+                if !skills_matched {
+                    candidates = pool.clone();
+                }
+                let participants = match self.selection_strategy {
+                    SelectionStrategy::Random => self.randomly_select_members(&candidates),
+                    SelectionStrategy::Fair => self.fairly_select_members(&candidates),
+                    SelectionStrategy::SocialMixing => self.socially_select_members(&candidates),
+                };
+                pool.retain(|member| !participants.contains(member));
+
+                let mut pending: Vec<AccountId> = Vec::new();
+                if self.acceptance_window > 0 {
+                    for member in &participants {
+                        if !pending.contains(member) {
+                            pending.push(*member);
+                        }
+                    }
+                }
+                let group = Group {
+                    task,
+                    participants,
+                    is_complete: false,
+                    pending,
+                };
+                groups.push((group, skills_matched));
+            }
+            assert!(!groups.is_empty(), "No task is selectable");
+
+            self.era_count = self.era_count.checked_add(1).expect("Era overflow");
+            self.last_selection = self.env().block_number();
+            self.last_selection_time = self.env().block_timestamp();
+            self.active_groups = Vec::new();
+            self.declined = Vec::new();
+
+            for (index, (group, skills_matched)) in groups.into_iter().enumerate() {
+                let mut counted: Vec<AccountId> = Vec::new();
+                for member in &group.participants {
+                    if counted.contains(member) {
+                        continue;
+                    }
+                    counted.push(*member);
+                    self.record_selection(*member);
+                }
+                self.group_history.push(counted);
+                self.prune_group_history();
+
+                self.task_waiting_since.insert(&group.task, &self.era_count);
+                self.active_groups.push(group.clone());
+
+                self.env().emit_event(NewEraStarted {
+                    era: Self::env().block_number(),
+                    group: index as u32,
+                    participants: group.participants,
+                    required_skills: self.get_task_skills(group.task.clone()),
+                    task: group.task,
+                    skills_matched,
+                });
+            }
+
+            self.reward_keeper();
+        }
+
+        // Hands every slot of `member` in `group` to a fresh member, who is added to the
+        // pending members if `needs_acceptance`. Slots no member is left for are dropped.
+        fn replace_participant(&mut self, group: usize, member: AccountId, needs_acceptance: bool) {
+            let active = &mut self.active_groups[group];
+            let slots = active
+                .participants
+                .iter()
+                .filter(|participant| **participant == member)
+                .count();
+            active
+                .participants
+                .retain(|participant| *participant != member);
+            active.pending.retain(|participant| *participant != member);
+            let task = active.task.clone();
+            self.declined.push(member);
+
+            let candidates = self.replacement_candidates(&task);
+            if candidates.is_empty() {
+                self.env().emit_event(ParticipantReplaced {
+                    era: self.last_selection,
+                    group: group as u32,
+                    replaced: member,
+                    replacement: None,
+                });
+                return;
+            }
+
+            // TODO: use randomness when chain extension is added
+            let offset = Self::env().block_number() as usize % candidates.len();
+            for i in 0..slots.min(candidates.len()) {
+                let replacement = candidates[(offset + i) % candidates.len()];
+                self.active_groups[group].participants.push(replacement);
+                if needs_acceptance {
+                    self.active_groups[group].pending.push(replacement);
+                }
+
+                self.record_selection(replacement);
+
+                self.env().emit_event(ParticipantReplaced {
+                    era: self.last_selection,
+                    group: group as u32,
+                    replaced: member,
+                    replacement: Some(replacement),
+                });
+            }
+        }
+
+        // Counts a selection of `member` in the current era, keeping the era the
+        // member was selected in before so the selection can be undone.
+        fn record_selection(&mut self, member: AccountId) {
+            if let Some(era) = self.last_selected_era.get(member) {
+                self.previous_selected_era.insert(member, &era);
+            } else {
+                self.previous_selected_era.remove(member);
+            }
+            self.last_selected_era.insert(member, &self.era_count);
+            let times = self.times_selected.get(member).unwrap_or(0);
+            self.times_selected.insert(member, &times.saturating_add(1));
+        }
+
+        // Undoes the selection of a member who declined or did not answer in time,
+        // so the member is not held back by cooldowns or fair selection.
+        fn undo_selection(&mut self, member: AccountId) {
+            if let Some(era) = self.previous_selected_era.take(member) {
+                self.last_selected_era.insert(member, &era);
+            } else {
+                self.last_selected_era.remove(member);
+            }
+            let times = self.times_selected.get(member).unwrap_or(0);
+            self.times_selected.insert(member, &times.saturating_sub(1));
+        }
+
+        // Eligible members outside every active group who did not decline this era,
+        // restricted to those qualified for `task` if there are any.
+        fn replacement_candidates(&self, task: &str) -> Vec<AccountId> {
+            // The era has already started, so the cooldown is checked against it
+            let candidates: Vec<AccountId> = self
+                .eligible_members(self.era_count)
+                .into_iter()
+                .filter(|member| {
+                    !self.declined.contains(member)
+                        && !self
+                            .active_groups
+                            .iter()
+                            .any(|group| group.participants.contains(member))
+                })
+                .collect();
+
+            let qualified = self.qualified_members(&candidates, task);
+            if qualified.is_empty() {
+                candidates
+            } else {
+                qualified
+            }
+        }
+
+        // Replaces the pending members of `group` without asking for acceptance,
+        // activating the group.
+        // Returns whether the group was still waiting for answers.
+        fn settle_group(&mut self, group: usize) -> bool {
+            if self.active_groups[group].pending.is_empty() {
+                return false;
+            }
+
+            for member in self.active_groups[group].pending.clone() {
+                self.undo_selection(member);
+                self.replace_participant(group, member, false);
+            }
+
+            self.env().emit_event(GroupActivated {
+                era: self.last_selection,
+                group: group as u32,
+            });
+            true
+        }
+
+        fn is_acceptance_window_open(&self) -> bool {
+            self.env().block_number() < self.last_selection.saturating_add(self.acceptance_window)
+        }
+
+        // Tips the caller for a keeper call, as far as the keeper pool allows.
+        fn reward_keeper(&mut self) {
+            let amount = self.keeper_tip.min(self.keeper_pool);
+            if amount == 0 {
+                return;
+            }
+
+            let keeper = self.env().caller();
+            self.keeper_pool -= amount;
+            self.env()
+                .transfer(keeper, amount)
+                .expect("Transfer failed");
+
+            self.env().emit_event(KeeperRewarded {
+                keeper,
+                era: self.last_selection,
+                amount,
+            });
+        }
+
+        /// Stores the caller's proof for the current era, replacing an earlier one.
+        #[ink(message)]
+        pub fn upload_completion_proof(
+            &mut self,
+            hash: Hash,
+            kind: ProofKind,
+            uri: Option<String>,
+        ) {
+            let caller = self.env().caller();
+
+            let group = self
+                .active_groups
+                .iter()
+                .position(|group| group.participants.contains(&caller))
+                .expect("Caller must be active participant");
+            assert!(
+                !self.active_groups[group].is_complete,
+                "Task already completed"
+            );
+
+            if let Some(uri) = &uri {
+                assert!(uri.len() <= MAX_PROOF_URI_LEN, "Proof URI too long");
+            }
+
+            let task = self.active_groups[group].task.clone();
+            let proof = Proof {
+                task: task.clone(),
+                hash,
+                uri,
+                kind,
+                timestamp: self.env().block_timestamp(),
+                approved: false,
+            };
+            self.proofs.insert((self.last_selection, caller), &proof);
+
+            self.env().emit_event(ProofUploaded {
+                era: self.last_selection,
+                participant: caller,
+                task: task.clone(),
+                kind,
+                hash,
+            });
+
+            if let Some(verifier) = self.task_verifiers.get(&task) {
+                let approved = proof_verifier::verify(verifier, task, caller, hash);
+                self.record_verification(group, caller, approved);
+            }
+        }
+
+        /// Marks the task of the active group at index `group` as completed, paying
+        /// out its bounties and minting badges to its participants.
+        #[ink(message)]
+        pub fn complete_task(&mut self, group: u32) {
+            self.ensure_owner();
+            self.ensure_not_paused(PauseCategory::Payouts);
+
+            assert!(
+                (group as usize) < self.active_groups.len(),
+                "Group does not exist"
+            );
+            if !self.is_group_active(group) {
+                assert!(!self.is_acceptance_window_open(), "Group not active");
+                self.settle_group(group as usize);
+            }
+            let active = &self.active_groups[group as usize];
+
+            if !active.is_complete {
+                // Everyone left, so the group is timed out with the next era instead
+                assert!(!active.participants.is_empty(), "Group has no participants");
+                self.ensure_proofs_uploaded(active);
+                self.finish_group(group as usize);
+            }
+        }
+
+        // Pays out and closes the task of `group`.
+        fn finish_group(&mut self, group: usize) {
+            let active = self.active_groups[group].clone();
+            let task = active.task;
+
+            let funds = self.task_info.get(&task).map_or(0, |info| info.1);
+            self.pay_out(&task, funds, &active.participants);
+            let proofs = self.group_proofs(&active.participants);
+            self.mint_badges(&task, &active.participants, &proofs);
+            self.record_occurrence(&task, &active.participants, proofs, funds);
+            self.clear_votes(&task);
+            self.end_funding_round(&task);
+            self.active_groups[group].is_complete = true;
+
+            if self.should_reopen(&task) {
+                self.reopen_task(task);
+            } else {
+                self.task_info.insert(&task, &(true, 0));
+            }
+        }
+
+        // Stores the verifier's verdict on the proof `participant` uploaded for `group`,
+        // completing the group once its approved proofs satisfy the task's rule.
+        fn record_verification(&mut self, group: usize, participant: AccountId, approved: bool) {
+            let key = (self.last_selection, participant);
+            let mut proof = self
+                .proofs
+                .get(key)
+                .expect("Proof existence verified before calling");
+            proof.approved = approved;
+            self.proofs.insert(key, &proof);
+
+            self.env().emit_event(ProofVerified {
+                era: self.last_selection,
+                participant,
+                task: proof.task,
+                approved,
+            });
+
+            let active = &self.active_groups[group];
+            if !approved
+                || active.is_complete
+                || !active.pending.is_empty()
+                || self.is_paused(PauseCategory::Payouts)
+            {
+                return;
+            }
+
+            let is_approved = |member: &AccountId| {
+                self.proofs
+                    .get((self.last_selection, *member))
+                    .is_some_and(|proof| proof.approved)
+            };
+            let satisfied = match self
+                .task_proof_rules
+                .get(&active.task)
+                .unwrap_or(ProofRule::Optional)
+            {
+                ProofRule::PerParticipant => active.participants.iter().all(is_approved),
+                ProofRule::Optional | ProofRule::PerGroup => {
+                    active.participants.iter().any(is_approved)
+                }
+            };
+            if satisfied {
+                self.finish_group(group);
+            }
+        }
+
+        fn attestation_hash(&self, task: &str, participant: AccountId, nonce: u64) -> [u8; 32] {
+            let mut output = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(
+                    self.env().account_id(),
+                    self.last_selection,
+                    task,
+                    participant,
+                    nonce,
+                ),
+                &mut output,
+            );
+            output
+        }
+
+        // Proofs uploaded in the current era by distinct `participants`
+        fn group_proofs(&self, participants: &[AccountId]) -> Vec<(AccountId, Hash)> {
+            let mut proofs: Vec<(AccountId, Hash)> = Vec::new();
+            for member in participants {
+                if proofs.iter().any(|(m, _)| m == member) {
+                    continue;
+                }
+                if let Some(proof) = self.proofs.get((self.last_selection, *member)) {
+                    proofs.push((*member, proof.hash));
+                }
+            }
+            proofs
+        }
+
+        fn record_occurrence(
+            &mut self,
+            task: &String,
+            participants: &[AccountId],
+            proofs: Vec<(AccountId, Hash)>,
+            funds: Balance,
+        ) {
+            let mut distinct: Vec<AccountId> = Vec::new();
+            for member in participants {
+                if !distinct.contains(member) {
+                    distinct.push(*member);
+                }
+            }
+
+            let index = self.task_occurrences.get(task).unwrap_or(0);
+            self.task_history.insert(
+                (task.clone(), index),
+                &Occurrence {
+                    era: self.last_selection,
+                    completed_at: self.env().block_number(),
+                    participants: distinct,
+                    proofs,
+                    funds,
+                },
+            );
+            self.task_occurrences
+                .insert(task, &index.checked_add(1).expect("Occurrence overflow"));
+        }
+
+        // Whether a just completed task has runs left according to its recurrence
+        fn should_reopen(&self, task: &String) -> bool {
+            match self.task_recurrence.get(task).unwrap_or(Recurrence::Once) {
+                Recurrence::Once => false,
+                Recurrence::Repeat {
+                    max_occurrences, ..
+                } => {
+                    let occurrences = self.task_occurrences.get(task).unwrap_or(0);
+                    max_occurrences.is_none_or(|max| occurrences < max)
+                }
+            }
+        }
+
+        // Opens a completed task for funding right away, and for selection once its
+        // recurrence delay has passed.
+        fn reopen_task(&mut self, task: String) {
+            let after_blocks = match self.task_recurrence.get(&task) {
+                Some(Recurrence::Repeat { after_blocks, .. }) => after_blocks,
+                _ => 0,
+            };
+            let available_at = self
+                .env()
+                .block_number()
+                .checked_add(after_blocks)
+                .expect("Block number overflow");
+
+            self.task_info.insert(&task, &(false, 0));
+            self.task_reopens_at.insert(&task, &available_at);
+
+            self.env().emit_event(TaskReopened { task, available_at });
+        }
+
+        // Mints one badge for `task` to every distinct participant.
+        fn mint_badges(
+            &mut self,
+            task: &str,
+            participants: &[AccountId],
+            proofs: &[(AccountId, Hash)],
+        ) {
+            let mut minted: Vec<AccountId> = Vec::new();
+
+            for member in participants.iter().cloned() {
+                if minted.contains(&member) {
+                    continue;
+                }
+                minted.push(member);
+
+                let id = self.badge_supply;
+                // Only the member's own proof, also when one proof covers the group
+                let proof = proofs
+                    .iter()
+                    .find(|(m, _)| *m == member)
+                    .map(|(_, hash)| *hash);
+                let badge = Badge {
+                    task: task.to_string(),
+                    era: self.last_selection,
+                    proof,
+                };
+                self.badges.insert(id, &(member, badge));
+                let balance = self.badge_balances.get(member).unwrap_or(0);
+                self.badge_balances.insert(member, &(balance + 1));
+                self.badge_supply = id.checked_add(1).expect("Badge id overflow");
+
+                self.env().emit_event(BadgeMinted {
+                    member,
+                    id: Id::U64(id),
+                    task: task.to_string(),
+                    era: self.last_selection,
+                });
+            }
+        }
+
+        // Splits the native and token bounties of `task` evenly between the
+        // participants, of which a finished group always has some. Whatever cannot
+        // be split evenly is left unclaimed.
+        fn pay_out(&mut self, task: &String, funds: Balance, participants: &[AccountId]) {
+            let count = participants.len() as Balance;
+
+            // Keeper fee, at most `MAX_KEEPER_FEE_BPS` so this can't overflow
+            let fee = funds / 10_000 * self.keeper_fee_bps as Balance
+                + funds % 10_000 * self.keeper_fee_bps as Balance / 10_000;
+            self.keeper_pool = self.keeper_pool.checked_add(fee).expect("Balance overflow");
+            let funds = funds - fee;
+
+            // A failed transfer must not block the payout of the whole group, so the
+            // share is kept for the participant to claim
+            let share = funds / count;
+            if share > 0 {
+                for participant in participants {
+                    if self.env().transfer(*participant, share).is_err() {
+                        let pending = self
+                            .pending_payouts
+                            .get(participant)
+                            .unwrap_or(0)
+                            .checked_add(share)
+                            .expect("Balance overflow");
+                        self.pending_payouts.insert(participant, &pending);
+                        self.env().emit_event(PayoutFailed {
+                            participant: *participant,
+                            amount: share,
+                        });
+                    }
+                }
+            }
+            self.unclaimed_funds = self
+                .unclaimed_funds
+                .checked_add(funds % count)
+                .expect("Balance overflow");
+
+            for (token, amount) in self.task_token_funds.take(task).unwrap_or_default() {
+                let share = amount / count;
+                if share > 0 {
+                    for participant in participants {
+                        if psp22::transfer(token, *participant, share).is_err() {
+                            let key = (*participant, token);
+                            let pending = self
+                                .pending_token_payouts
+                                .get(key)
+                                .unwrap_or(0)
+                                .checked_add(share)
+                                .expect("Balance overflow");
+                            self.pending_token_payouts.insert(key, &pending);
+                            self.env().emit_event(TokenPayoutFailed {
+                                token,
+                                participant: *participant,
+                                amount: share,
+                            });
+                        }
+                    }
+                }
+                self.add_unclaimed_token_funds(token, amount % count);
+            }
+        }
+
+        fn delete_task(&mut self, task: &String) {
+            self.release_task(task);
+
+            // Search for index of task
+            let index = self
+                .tasks
+                .iter()
+                .position(|x| x == task)
+                .expect("Task existence verified before calling");
+            self.tasks.swap_remove(index);
+        }
+
+        // Deletes everything stored for a removed task. If the task is funded, its
+        // native and token funds are added to the unclaimed funds.
+        fn release_task(&mut self, task: &String) {
+            let task_info = self
+                .task_info
+                .take(task)
+                .expect("Task existence verified before calling");
+            self.unclaimed_funds = self
+                .unclaimed_funds
+                .checked_add(task_info.1)
+                .expect("Balance overflow");
+
+            for (token, amount) in self.task_token_funds.take(task).unwrap_or_default() {
+                self.add_unclaimed_token_funds(token, amount);
+            }
+            self.task_tags.remove(task);
+            self.task_skills.remove(task);
+            self.task_waiting_since.remove(task);
+            self.clear_votes(task);
+            self.proposed_tasks.remove(task);
+            self.task_recurrence.remove(task);
+            // Runs left in `task_history` are out of range once the count is gone
+            // and get overwritten if a task of the same name completes again
+            self.task_occurrences.remove(task);
+            self.task_reopens_at.remove(task);
+            self.task_windows.remove(task);
+            self.end_funding_round(task);
+            self.funding_goals.remove(task);
+            self.task_proof_rules.remove(task);
+            self.task_verifiers.remove(task);
+            self.task_oracles.remove(task);
+        }
+
+        // Funding round `task` collects donations in, starting a new one if needed.
+        fn funding_round(&mut self, task: &String) -> u32 {
+            if let Some(round) = self.task_funding_rounds.get(task) {
+                return round;
+            }
+            let round = self.funding_rounds;
+            self.funding_rounds = round.checked_add(1).expect("Round overflow");
+            self.task_funding_rounds.insert(task, &round);
+            round
+        }
+
+        // Ends the funding round of `task` once its donations are paid out, refunded or
+        // unclaimed. Donations and pledges stay under the old round, so they can't be
+        // reclaimed from the task anymore.
+        fn end_funding_round(&mut self, task: &String) {
+            self.task_funding_rounds.remove(task);
+            self.task_donated.remove(task);
+        }
+
+        // Whether `task` expired before being completed. Tasks worked on by an
+        // unfinished group never count as expired.
+        fn is_expired(&self, task: &String) -> bool {
+            let expired = self
+                .task_windows
+                .get(task)
+                .and_then(|(_, expires_at)| expires_at)
+                .is_some_and(|at| self.env().block_number() >= at);
+            let completed = self.task_info.get(task).is_some_and(|info| info.0);
+            let active = self
+                .active_groups
+                .iter()
+                .any(|group| !group.is_complete && group.task == *task);
+
+            expired && !completed && !active
+        }
+
+        // Lets the donors of `task` claim back their native and token donations in its
+        // funding round, leaving the task without donated funds. Returns the refunded
+        // native amount.
+        fn refund_donations(&mut self, task: &String) -> Balance {
+            let Some(round) = self.task_funding_rounds.get(task) else {
+                return 0;
+            };
+            self.refundable_rounds.insert(round, &());
+
+            let refunded = self.task_donated.get(task).unwrap_or(0);
+            if let Some((completed, funds)) = self.task_info.get(task) {
+                self.task_info
+                    .insert(task, &(completed, funds.saturating_sub(refunded)));
+            }
+
+            // Token funds only come from donations
+            self.task_token_funds.remove(task);
+
+            refunded
+        }
+
+        // Deletes everything stored for a removed member.
+        fn release_member(&mut self, member: AccountId) {
+            self.is_member.remove(member);
+            self.member_skills.remove(member);
+            self.unavailable_until.remove(member);
+            self.last_selected_era.remove(member);
+            self.times_selected.remove(member);
+            self.previous_selected_era.remove(member);
+
+            for task in self.member_votes.take(member).unwrap_or_default() {
+                if let Some(mut voters) = self.task_voters.get(&task) {
+                    voters.retain(|voter| *voter != member);
+                    self.task_voters.insert(&task, &voters);
+                }
+            }
+        }
+
+        // Removes the votes for `task` from its voters' votes.
+        fn clear_votes(&mut self, task: &String) {
+            for voter in self.task_voters.take(task).unwrap_or_default() {
+                if let Some(mut votes) = self.member_votes.get(voter) {
+                    votes.retain(|voted| voted != task);
+                    self.member_votes.insert(voter, &votes);
+                }
+            }
+        }
+
+        // Members that are neither on an availability break nor cooling down from
+        // their last selection in `era`
+        fn eligible_members(&self, era: u32) -> Vec<AccountId> {
+            let block = self.env().block_number();
+
+            self.members
+                .iter()
+                .filter(|member| {
+                    self.unavailable_until
+                        .get(**member)
+                        .is_none_or(|until| block >= until)
+                        && self
+                            .last_selected_era
+                            .get(**member)
+                            .is_none_or(|last| era >= last.saturating_add(self.selection_cooldown))
+                })
+                .cloned()
+                .collect()
+        }
+
+        fn normalize_skills(mut skills: Vec<String>) -> Vec<String> {
+            skills.sort();
+            skills.dedup();
+            assert!(skills.len() <= MAX_SKILLS, "Too many skills");
+            skills
+        }
+
+        // Members of `eligible` having every skill `task` requires
+        fn qualified_members(&self, eligible: &[AccountId], task: &str) -> Vec<AccountId> {
+            let required = self.task_skills.get(task).unwrap_or_default();
+            if required.is_empty() {
+                return eligible.to_vec();
+            }
+
+            eligible
+                .iter()
+                .filter(|member| {
+                    let skills = self.get_skills(**member);
+                    required.iter().all(|skill| skills.contains(skill))
+                })
+                .cloned()
+                .collect()
+        }
+
+        // Qualified members a task needs to be staffed without falling back
+        fn participants_needed(eligible: &[AccountId]) -> usize {
+            PARTICIPANTS_PER_ERA.min(eligible.len())
+        }
+
+        fn add_unclaimed_token_funds(&mut self, token: AccountId, amount: Balance) {
+            let unclaimed = self
+                .unclaimed_token_funds
+                .get(token)
+                .unwrap_or(0)
+                .checked_add(amount)
+                .expect("Balance overflow");
+            self.unclaimed_token_funds.insert(token, &unclaimed);
+        }
+
+        // Pops up to `limit` members off the back of `members`, so the remaining
+        // length doubles as the sweep cursor.
+        fn sweep_members(&mut self, limit: usize) -> usize {
+            // Cleared members must not stay in groups that can still be paid out
+            assert!(
+                self.active_groups
+                    .iter()
+                    .all(|group| group.is_complete || group.participants.is_empty()),
+                "Active task must be completed"
+            );
+
+            for _ in 0..limit {
+                match self.members.pop() {
+                    Some(member) => self.release_member(member),
+                    None => break,
+                }
+            }
+
+            let remaining = self.members.len();
+            self.clearing_members = remaining > 0;
+            if remaining == 0 {
+                self.env().emit_event(MembersCleared {});
+            }
+
+            remaining
+        }
+
+        // Pops up to `limit` tasks off the back of `tasks`, moving their funds to
+        // `unclaimed_funds`.
+        fn sweep_tasks(&mut self, limit: usize) -> usize {
+            for _ in 0..limit {
+                let task = match self.tasks.pop() {
+                    Some(task) => task,
+                    None => break,
+                };
+                self.release_task(&task);
+            }
+
+            let remaining = self.tasks.len();
+            self.clearing_tasks = remaining > 0;
+            if remaining == 0 {
+                self.env().emit_event(TasksCleared {});
+            }
+
+            remaining
+        }
+
+        // Voters of `task`, which must exist and not be completed yet
+        fn votable_task_voters(&self, task: &String) -> Vec<AccountId> {
+            // Ensure that the task does exists
+            assert!(self.task_info.contains(task), "Task does not exist");
+
+            let task_info = self
+                .task_info
+                .get(task)
+                .expect("Task existence verified before calling");
+
+            assert!(!task_info.0, "Task already completed");
+
+            self.task_voters.get(task).unwrap_or_default()
+        }
+
+        fn store_task_tags(&mut self, task: String, mut tags: Vec<String>) {
+            tags.sort();
+            tags.dedup();
+            assert!(tags.len() <= MAX_TAGS_PER_TASK, "Too many tags");
+            for tag in &tags {
+                self.ensure_category_exists(tag);
+            }
+
+            self.task_tags.insert(&task, &tags);
+
+            self.env().emit_event(TaskTagsSet { task, tags });
+        }
+
+        // Tasks `randomly_select_task` may draw from in the current era
+        fn selectable_tasks(&self) -> Vec<String> {
+            self.tasks
+                .iter()
+                .filter(|task| self.is_selectable(task))
+                .cloned()
+                .collect()
+        }
+
+        fn is_selectable(&self, task: &str) -> bool {
+            if self.proposed_tasks.contains(task) {
+                return false;
+            }
+
+            // Completed tasks are done, reopened ones wait for their recurrence delay
+            if self.task_info.get(task).is_none_or(|info| info.0) {
+                return false;
+            }
+            let block = self.env().block_number();
+            if self.task_reopens_at.get(task).is_some_and(|at| block < at) {
+                return false;
+            }
+
+            // Tasks below their funding target, if targets are enforced
+            if self.funding_goals_required && !self.is_funding_goal_met(task) {
+                return false;
+            }
+
+            // Tasks outside their availability window
+            if let Some((available_from, expires_at)) = self.task_windows.get(task) {
+                if available_from.is_some_and(|from| block < from)
+                    || expires_at.is_some_and(|until| block >= until)
+                {
+                    return false;
+                }
+            }
+
+            self.era_categories.is_empty()
+                || self
+                    .task_tags
+                    .get(task)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|tag| self.era_categories.contains(tag))
+        }
+
+        fn add_round_contribution(&mut self, task: &String, donor: AccountId, amount: Balance) {
+            let contribution = self
+                .round_contribution(task, donor)
+                .checked_add(amount)
+                .expect("Balance overflow");
+            self.set_round_contribution(task, donor, contribution);
+        }
+
+        // Native donations of `donor` to `task` during the open matching round
+        fn round_contribution(&self, task: &str, donor: AccountId) -> Balance {
+            match self.round_contributions.get((String::from(task), donor)) {
+                Some((round, amount))
+                    if self.matching_round_open && round == self.matching_rounds =>
+                {
+                    amount
+                }
+                _ => 0,
+            }
+        }
+
+        // Replaces the contribution of `donor` to `task` in the open matching round,
+        // updating the score of the task accordingly.
+        fn set_round_contribution(&mut self, task: &String, donor: AccountId, amount: Balance) {
+            let previous = self.round_contribution(task, donor);
+            let (roots, sum) = match self.round_scores.get(task) {
+                Some(score) => score,
+                None => {
+                    self.round_tasks.push(task.clone());
+                    (0, 0)
+                }
+            };
+            let roots = roots
+                .saturating_sub(previous.isqrt())
+                .checked_add(amount.isqrt())
+                .expect("Balance overflow");
+            let sum = sum
+                .saturating_sub(previous)
+                .checked_add(amount)
+                .expect("Balance overflow");
+            self.round_scores.insert(task, &(roots, sum));
+            self.round_contributions
+                .insert((task.clone(), donor), &(self.matching_rounds, amount));
+        }
+
+        // Quadratic funding match of contributions whose square roots add up to
+        // `roots` and which add up to `sum`: roots^2 - sum
+        fn quadratic_match(roots: Balance, sum: Balance) -> Balance {
+            roots
+                .checked_mul(roots)
+                .expect("Balance overflow")
+                .saturating_sub(sum)
+        }
+
+        fn is_funding_goal_met(&self, task: &str) -> bool {
+            match self.funding_goals.get(task).and_then(|(target, _)| target) {
+                Some(target) => self
+                    .task_info
+                    .get(task)
+                    .is_some_and(|info| info.1 >= target),
+                None => true,
+            }
+        }
+
+        fn randomly_select_members(&self, candidates: &[AccountId]) -> Vec<AccountId> {
+            // TODO: use randomness when chain extension is added
+
+            let mut members: Vec<AccountId> = Vec::new();
+            for i in 0..PARTICIPANTS_PER_ERA {
+                let member =
+                    candidates[(Self::env().block_number() as usize + i) % candidates.len()];
+                members.push(member);
+            }
+
+            members
+        }
+
+        // Picks the candidates selected longest ago, never selected ones first. Ties
+        // go to the candidates with the largest deficit, i.e. the fewest selections,
+        // and then to a rotating offset.
+        fn fairly_select_members(&self, candidates: &[AccountId]) -> Vec<AccountId> {
+            let offset = Self::env().block_number() as usize % candidates.len();
+
+            let mut ranked: Vec<(Option<u32>, u32, usize, AccountId)> = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, member)| {
+                    let rotation = (i + candidates.len() - offset) % candidates.len();
+                    (
+                        self.last_selected_era.get(member),
+                        self.times_selected.get(member).unwrap_or(0),
+                        rotation,
+                        *member,
+                    )
+                })
+                .collect();
+            ranked.sort();
+
+            (0..PARTICIPANTS_PER_ERA)
+                .map(|i| ranked[i % ranked.len()].3)
+                .collect()
+        }
+
+        // Greedily builds a group starting from a rotating candidate, each time adding
+        // the candidate with the fewest past pairings with the members picked so far.
+        fn socially_select_members(&self, candidates: &[AccountId]) -> Vec<AccountId> {
+            let offset = Self::env().block_number() as usize % candidates.len();
+            let mut remaining: Vec<AccountId> = (0..candidates.len())
+                .map(|i| candidates[(offset + i) % candidates.len()])
+                .collect();
+
+            let mut group: Vec<AccountId> = Vec::new();
+            group.push(remaining.remove(0));
+            while group.len() < PARTICIPANTS_PER_ERA && !remaining.is_empty() {
+                let (index, _) = remaining
+                    .iter()
+                    .map(|candidate| {
+                        group
+                            .iter()
+                            .map(|member| self.get_pair_count(*candidate, *member))
+                            .sum::<u32>()
+                    })
+                    .enumerate()
+                    .min_by_key(|(_, pairings)| *pairings)
+                    .expect("Remaining candidates checked to be non-empty");
+                group.push(remaining.remove(index));
+            }
+
+            (0..PARTICIPANTS_PER_ERA)
+                .map(|i| group[i % group.len()])
+                .collect()
+        }
+
+        fn prune_group_history(&mut self) {
+            let limit = self.group_history_limit as usize;
+            if self.group_history.len() > limit {
+                let excess = self.group_history.len() - limit;
+                self.group_history.drain(..excess);
+            }
+        }
+
+        // Draws a selectable task not in `assigned`, or `None` if there is none left.
+        fn randomly_select_task(
+            &self,
+            eligible: &[AccountId],
+            assigned: &[String],
+        ) -> Option<String> {
+            self.ensure_non_empty_tasks();
+
+            let tasks: Vec<String> = self
+                .selectable_tasks()
+                .into_iter()
+                .filter(|task| !assigned.contains(task))
+                .collect();
+            if tasks.is_empty() {
+                return None;
+            }
+
+            let drawn = self.draw_task_index(&tasks);
+
+            // Re-draw by weight among the tasks enough members have the skills for,
+            // keeping the first draw if no task can be staffed
+            let needed = Self::participants_needed(eligible);
+            let staffable: Vec<String> = tasks
+                .iter()
+                .filter(|task| self.qualified_members(eligible, task).len() >= needed)
+                .cloned()
+                .collect();
+            if staffable.is_empty() || staffable.contains(&tasks[drawn]) {
+                return Some(tasks[drawn].clone());
+            }
+            Some(staffable[self.draw_task_index(&staffable)].clone())
+        }
+
+        // Draws the index of a task from `tasks` with odds proportional to its weight,
+        // falling back to uniform odds if every task weighs nothing.
+        fn draw_task_index(&self, tasks: &[String]) -> usize {
+            // TODO: use randomness
+            // Hashed so that the seed covers the whole weight range, not only the
+            // first `block_number` units of it
+            let mut output = [0u8; 16];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x128, _>(
+                &(self.env().block_number(), self.era_count),
+                &mut output,
+            );
+            let seed = u128::from_le_bytes(output);
+
+            let weights: Vec<u128> = tasks.iter().map(|task| self.task_weight(task)).collect();
+            let total = weights
+                .iter()
+                .try_fold(0u128, |total, weight| total.checked_add(*weight))
+                .expect("Weight overflow");
+            if total == 0 {
+                return (seed % tasks.len() as u128) as usize;
+            }
+
+            let mut point = seed % total;
+            for (index, weight) in weights.iter().enumerate() {
+                if point < *weight {
+                    return index;
+                }
+                point -= weight;
+            }
+            unreachable!("Point is below the total weight")
+        }
+
+        fn task_weight(&self, task: &str) -> u128 {
+            let weight = match self.task_weighting {
+                TaskWeighting::Uniform => 1,
+                TaskWeighting::Bounty => self.task_info.get(task).map_or(0, |info| info.1),
+                TaskWeighting::Age => {
+                    let since = self.task_waiting_since.get(task).unwrap_or(self.era_count);
+                    1 + (self.era_count - since) as u128
+                }
+                TaskWeighting::Votes => self
+                    .task_voters
+                    .get(task)
+                    .map_or(0, |voters| voters.len() as u128),
+            };
+
+            match self.task_weight_cap {
+                Some(cap) => weight.min(cap),
+                None => weight,
+            }
+        }
+
+        // Badges are only ever minted with `Id::U64` ids.
+        fn badge_id(id: Id) -> Option<u64> {
+            match id {
+                Id::U64(id) => Some(id),
+                _ => None,
+            }
+        }
+
+        fn ensure_owner(&self) {
+            assert_eq!(self.env().caller(), self.owner, "Only owner can call");
+        }
+
+        fn ensure_owner_or_pauser(&self) {
+            let caller = self.env().caller();
+            assert!(
+                caller == self.owner || caller == self.pauser,
+                "Only owner or pauser can call"
+            );
+        }
+
+        // Withdrawals of user funds must not call this, so they stay available
+        // during an emergency stop.
+        fn ensure_not_paused(&self, category: PauseCategory) {
+            assert!(!self.is_paused(category), "Operation is paused");
+        }
+
+        fn ensure_owner_or_curator(&self) {
+            let caller = self.env().caller();
+            assert!(
+                caller == self.owner || self.curators.contains(caller),
+                "Only owner or curator can call"
+            );
+        }
+
+        fn ensure_category_exists(&self, category: &String) {
+            assert!(
+                self.categories.contains(category),
+                "Category does not exist"
+            );
+        }
+
+        fn ensure_member(&self) {
+            assert!(
+                self.is_member.contains(self.env().caller()),
+                "Must be a member to call"
+            );
+        }
+
+        fn ensure_non_empty_members(&self) {
+            assert!(!self.members.is_empty(), "Must have at least one member");
+        }
+
+        fn ensure_non_empty_tasks(&self) {
+            assert!(!self.tasks.is_empty(), "Must have at least one task");
+        }
+
+        // Every active group must have completed its task, unless the group timeout
+        // passed, in which case unfinished groups are reported as timed out.
+        // Groups left without participants cannot complete, so they never hold up
+        // the next era.
+        fn ensure_active_groups_finished(&self) {
+            assert!(
+                self.active_groups
+                    .iter()
+                    .all(|group| group.is_complete || group.participants.is_empty())
+                    || self.is_timed_out(),
+                "Active task must be completed"
+            );
+        }
+
+        // Whether the group timeout has passed since the current era started
+        fn is_timed_out(&self) -> bool {
+            self.group_timeout > 0
+                && self.env().block_number()
+                    >= self.last_selection.saturating_add(self.group_timeout)
+        }
+
+        // Gives up on the groups of the current era that did not complete their task.
+        fn time_out_groups(&mut self) {
+            for (index, group) in self.active_groups.iter().enumerate() {
+                if group.is_complete {
+                    continue;
+                }
+
+                self.env().emit_event(GroupTimedOut {
+                    era: self.last_selection,
+                    group: index as u32,
+                    task: group.task.clone(),
+                });
+            }
+        }
+
+        // The participants of `group` must have uploaded the proofs its task's rule asks for.
+        fn ensure_proofs_uploaded(&self, group: &Group) {
+            let uploaded =
+                |member: &AccountId| self.proofs.contains((self.last_selection, *member));
+
+            match self
+                .task_proof_rules
+                .get(&group.task)
+                .unwrap_or(ProofRule::Optional)
+            {
+                ProofRule::Optional => {}
+                ProofRule::PerGroup => assert!(
+                    group.participants.iter().any(uploaded),
+                    "Missing completion proof"
+                ),
+                ProofRule::PerParticipant => assert!(
+                    group.participants.iter().all(uploaded),
+                    "Missing completion proof"
+                ),
+            }
+        }
+
+        fn ensure_acceptance_window_open(&self) {
+            assert!(self.is_acceptance_window_open(), "Acceptance window closed");
+        }
+
+        fn ensure_not_clearing_members(&self) {
+            assert!(!self.clearing_members, "Members are being cleared");
+        }
+
+        fn ensure_not_clearing_tasks(&self) {
+            assert!(!self.clearing_tasks, "Tasks are being cleared");
+        }
+
+        fn ensure_era_reached(&self) {
+            let reached = match self.schedule {
+                Schedule::Blocks => {
+                    self.env().block_number()
+                        >= self.last_selection.saturating_add(self.next_selection)
+                }
+                Schedule::Timestamp { .. } => self.env().block_timestamp() >= self.next_era_time(),
+            };
+            assert!(reached, "Selection era not reached");
+        }
+
+        // First scheduled timestamp after the last era started, for timestamp schedules
+        fn next_era_time(&self) -> u64 {
+            let Schedule::Timestamp { period, offset } = self.schedule else {
+                unreachable!("Only called for timestamp schedules")
+            };
+
+            let after = self.last_selection_time;
+            if after < offset {
+                return offset;
+            }
+            let periods = (after - offset) / period + 1;
+            offset
+                .checked_add(periods.checked_mul(period).expect("Timestamp overflow"))
+                .expect("Timestamp overflow")
+        }
+    }
+
+    impl psp34::PSP34 for Polkapobal {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(AsRef::<[u8]>::as_ref(&self.env().account_id()).to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            self.badge_balances.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            let id = Self::badge_id(id)?;
+            self.badges.get(id).map(|(owner, _)| owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, _owner: AccountId, _operator: AccountId, _id: Option<Id>) -> bool {
+            false
+        }
+
+        // Badges are soulbound, so they can be neither approved nor transferred.
+        #[ink(message)]
+        fn approve(
+            &mut self,
+            _operator: AccountId,
+            _id: Option<Id>,
+            _approved: bool,
+        ) -> Result<(), PSP34Error> {
+            Err(PSP34Error::Custom(String::from(
+                "Badges are non-transferable",
+            )))
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, _to: AccountId, _id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            Err(PSP34Error::Custom(String::from(
+                "Badges are non-transferable",
+            )))
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.badge_supply as Balance
+        }
+    }
+
+    impl psp34::PSP34Metadata for Polkapobal {
+        /// Supported keys are `task`, `era` and `proof`.
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            let id = Self::badge_id(id)?;
+            let (_, badge) = self.badges.get(id)?;
+
+            match key.as_slice() {
+                b"task" => Some(badge.task.into_bytes()),
+                b"era" => Some(badge.era.to_string().into_bytes()),
+                b"proof" => badge
+                    .proof
+                    .map(|proof| AsRef::<[u8]>::as_ref(&proof).to_vec()),
+                _ => None,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ink::codegen::Env;
+        use ink::env::test::{self};
+
+        const DEFAULT_SELECTION_ERA: u32 = 10;
+
+        fn create_default_contract() -> Polkapobal {
+            Polkapobal::new(DEFAULT_SELECTION_ERA)
+        }
+
+        fn set_balance(account_id: AccountId, balance: Balance) {
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account_id, balance)
+        }
+
+        fn get_balance(account_id: AccountId) -> Balance {
+            ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account_id)
+                .expect("Cannot get account balance")
+        }
+
+        fn advance_block(num_blocks: u32) {
+            for _ in 0..num_blocks {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+        }
+
+        #[ink::test]
+        fn construction_works() {
+            let init_block = 5;
+            // non-zero block number to test
+            advance_block(init_block);
+
+            let expected = Polkapobal {
+                owner: AccountId::from([0x01; 32]),
+                pauser: AccountId::from([0x01; 32]),
+                paused: false,
+                paused_categories: Mapping::default(),
+                members: Vec::new(),
+                is_member: Mapping::default(),
+                member_skills: Mapping::default(),
+                unavailable_until: Mapping::default(),
+                last_selected_era: Mapping::default(),
+                times_selected: Mapping::default(),
+                previous_selected_era: Mapping::default(),
+                selection_strategy: SelectionStrategy::Random,
+                selection_cooldown: 0,
+                era_count: 0,
+                group_history: Vec::new(),
+                group_history_limit: DEFAULT_GROUP_HISTORY_LIMIT,
+                tasks: Vec::new(),
+                task_info: Mapping::default(),
+                task_tags: Mapping::default(),
+                task_skills: Mapping::default(),
+                curators: Mapping::default(),
+                categories: Vec::new(),
+                era_categories: Vec::new(),
+                task_recurrence: Mapping::default(),
+                task_history: Mapping::default(),
+                task_occurrences: Mapping::default(),
+                task_reopens_at: Mapping::default(),
+                task_windows: Mapping::default(),
+                funding_goals: Mapping::default(),
+                funding_goals_required: false,
+                matching_round_open: false,
+                matching_pool: 0,
+                matching_rounds: 0,
+                round_tasks: Vec::new(),
+                round_contributions: Mapping::default(),
+                round_scores: Mapping::default(),
+                funding_rounds: 0,
+                task_funding_rounds: Mapping::default(),
+                task_donated: Mapping::default(),
+                task_donations: Mapping::default(),
+                pledges: Mapping::default(),
+                refundable_rounds: Mapping::default(),
+                pending_payouts: Mapping::default(),
+                pending_token_payouts: Mapping::default(),
+                expiry_cursor: 0,
+                task_voters: Mapping::default(),
+                member_votes: Mapping::default(),
+                task_review: false,
+                approval_threshold: 0,
+                proposed_tasks: Mapping::default(),
+                task_waiting_since: Mapping::default(),
+                task_weighting: TaskWeighting::Uniform,
+                task_weight_cap: None,
+                unclaimed_funds: 0,
+                accepted_tokens: Mapping::default(),
+                task_token_funds: Mapping::default(),
+                task_token_donations: Mapping::default(),
+                unclaimed_token_funds: Mapping::default(),
+                start_block: init_block,
+                next_selection: DEFAULT_SELECTION_ERA,
+                last_selection: init_block,
+                schedule: Schedule::Blocks,
+                last_selection_time: ink::env::block_timestamp::<Environment>(),
+                active_groups: Vec::new(),
+                groups_per_era: 1,
+                acceptance_window: 0,
+                declined: Vec::new(),
+                group_timeout: 0,
+                keeper_pool: 0,
+                keeper_tip: 0,
+                keeper_fee_bps: 0,
+                proofs: Mapping::default(),
+                task_verifiers: Mapping::default(),
+                oracles: Mapping::default(),
+                task_oracles: Mapping::default(),
+                attestation_nonces: Mapping::default(),
+                task_proof_rules: Mapping::default(),
+                badges: Mapping::default(),
+                badge_balances: Mapping::default(),
+                badge_supply: 0,
+                clearing_members: false,
+                clearing_tasks: false,
+            };
+
+            let contract = Polkapobal::new(DEFAULT_SELECTION_ERA);
+            assert_eq!(contract.owner, expected.owner);
+            assert_eq!(contract.pauser, expected.pauser);
+            assert_eq!(contract.paused, expected.paused);
+            assert_eq!(contract.members.len(), 0);
+            assert_eq!(contract.tasks.len(), 0);
+            assert_eq!(contract.unclaimed_funds, expected.unclaimed_funds);
+            assert_eq!(contract.start_block, expected.start_block);
+            assert_eq!(contract.next_selection, expected.next_selection);
+            assert_eq!(contract.last_selection, expected.last_selection);
+            assert_eq!(contract.schedule, expected.schedule);
+            assert_eq!(contract.last_selection_time, expected.last_selection_time);
+            assert_eq!(contract.active_groups.len(), 0);
+            assert_eq!(contract.groups_per_era, 1);
+        }
+
+        #[ink::test]
+        fn timestamp_schedule_works() {
+            const HOUR: u64 = 60 * 60 * 1000;
+
+            ink::env::test::set_block_timestamp::<Environment>(5 * HOUR);
+            // Every 24 hours at 12:00
+            let mut contract = Polkapobal::new_with_timestamp_schedule(24 * HOUR, 12 * HOUR);
+            let block = contract.env().block_number();
+            assert_eq!(
+                contract.get_next_era_start(),
+                (
+                    block + (7 * HOUR / EXPECTED_BLOCK_TIME_MS) as u32,
+                    12 * HOUR
+                )
+            );
+
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+
+            ink::env::test::set_block_timestamp::<Environment>(12 * HOUR);
+            contract.start_new_era();
+            contract.complete_task(0);
+            assert_eq!(contract.get_next_era_start().1, 36 * HOUR);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Selection era not reached")]
+        fn timestamp_schedule_before_start_panics() {
+            const HOUR: u64 = 60 * 60 * 1000;
+
+            let mut contract = Polkapobal::new_with_timestamp_schedule(24 * HOUR, 12 * HOUR);
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+
+            // Blocks passing don't matter
+            advance_block(1_000);
+            ink::env::test::set_block_timestamp::<Environment>(12 * HOUR - 1);
+            contract.start_new_era();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Eras follow a timestamp schedule")]
+        fn set_selection_era_with_timestamp_schedule_panics() {
+            let mut contract = Polkapobal::new_with_timestamp_schedule(1_000, 0);
+
+            contract.set_selection_era(20);
+        }
+
+        #[ink::test]
+        fn set_selection_era_works() {
+            let mut contract = create_default_contract();
+
+            contract.set_selection_era(20);
+
+            assert_eq!(contract.next_selection, 20);
+            assert_eq!(test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn pause_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.set_pauser(accounts.bob);
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.pause();
+            assert!(contract.is_paused(PauseCategory::Funding));
+            assert!(contract.is_paused(PauseCategory::Payouts));
+
+            contract.unpause();
+            assert!(!contract.is_paused(PauseCategory::Funding));
+
+            contract.pause_category(PauseCategory::Eras);
+            assert!(contract.is_paused(PauseCategory::Eras));
+            assert!(!contract.is_paused(PauseCategory::Membership));
+
+            // Other categories keep working
+            contract.register_member();
+
+            contract.unpause_category(PauseCategory::Eras);
+            assert!(!contract.is_paused(PauseCategory::Eras));
+            assert_eq!(test::recorded_events().count(), 5);
+        }
+
+        #[ink::test]
+        fn register_member_works() {
+            let mut contract = create_default_contract();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+
+            assert_eq!(contract.members.len(), 2);
+            assert!(contract.members.contains(&accounts.alice));
+            assert!(contract.members.contains(&accounts.bob));
+            assert!(contract.is_member.contains(accounts.alice));
+            assert!(contract.is_member.contains(accounts.bob));
+            assert_eq!(test::recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn deregister_member_works() {
+            let mut contract = create_default_contract();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+
+            assert_eq!(contract.members.len(), 3);
+            contract.deregister_member();
+            assert_eq!(contract.members.len(), 2);
+            assert!(!contract.members.contains(&accounts.charlie));
+            assert!(!contract.is_member.contains(accounts.charlie));
+            assert!(!contract.member_skills.contains(accounts.charlie));
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.deregister_member();
+            assert_eq!(contract.members.len(), 1);
+            assert!(!contract.members.contains(&accounts.bob));
+            assert!(!contract.is_member.contains(accounts.bob));
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.deregister_member();
+            assert_eq!(contract.members.len(), 0);
+            assert!(!contract.members.contains(&accounts.alice));
+            assert!(!contract.is_member.contains(accounts.alice));
+            assert_eq!(test::recorded_events().count(), 6);
+        }
+
+        #[ink::test]
+        fn deregister_active_participant_replaces_them() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 6);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let selected = contract.active_groups[0].participants.clone();
+
+            ink::env::test::set_caller::<Environment>(selected[0]);
+            contract.deregister_member();
+
+            let group = contract.active_groups[0].clone();
+            assert!(!contract.members.contains(&selected[0]));
+            assert!(!group.participants.contains(&selected[0]));
+            assert_eq!(group.participants.len(), 4);
+            let replacement = group.participants[3];
+            assert!(contract.members.contains(&replacement));
+            assert!(!selected.contains(&replacement));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Caller must be active participant")]
+        fn deregistered_participant_cannot_upload_proof() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 4);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let selected = contract.active_groups[0].participants.clone();
+
+            // No member is left to take over the slot
+            ink::env::test::set_caller::<Environment>(selected[0]);
+            contract.deregister_member();
+            assert_eq!(contract.active_groups[0].participants.len(), 3);
+
+            contract.upload_completion_proof(Hash::from([1; 32]), ProofKind::Photo, None);
+        }
+
+        #[ink::test]
+        fn clear_members_works() {
+            let mut contract = create_default_contract();
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+
+            assert_eq!(contract.members.len(), 3);
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.clear_members();
+
+            assert_eq!(contract.members.len(), 0);
+            assert!(!contract.is_member.contains(accounts.alice));
+            assert!(!contract.is_member.contains(accounts.bob));
+            assert!(!contract.is_member.contains(accounts.charlie));
+            assert_eq!(test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn clear_members_batch_works() {
+            let mut contract = create_default_contract();
+
+            for i in 0..5u8 {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.register_member();
+            }
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+
+            assert_eq!(contract.clear_members_batch(2), 3);
+            assert!(contract.clearing_members);
+            assert_eq!(contract.clear_members_batch(2), 1);
+            // `MembersCleared` is not emitted until the sweep finishes
+            assert_eq!(test::recorded_events().count(), 5);
+
+            assert_eq!(contract.clear_members_batch(2), 0);
+            assert!(!contract.clearing_members);
+            assert_eq!(contract.members.len(), 0);
+            for i in 0..5u8 {
+                assert!(!contract.is_member.contains(AccountId::from([i; 32])));
+            }
+            assert_eq!(test::recorded_events().count(), 6);
+
+            // Member operations are available again
+            contract.register_member();
+            assert_eq!(contract.members.len(), 1);
+        }
+
+        #[ink::test]
+        fn add_task_works() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+
+            let task1 = String::from("Task 1");
+            let task2 = String::from("Task 2");
+            contract.add_task(task1.clone());
+            contract.add_task(task2.clone());
+
+            assert_eq!(contract.tasks.len(), 2);
+            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 0));
+            assert_eq!(contract.task_info.get(&task2).unwrap(), (false, 0));
+            assert_eq!(test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn task_tags_work() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.add_curator(accounts.bob);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.add_category(String::from("outdoor"));
+            contract.add_category(String::from("coding"));
+
+            contract.register_member();
+            let task1 = String::from("Task 1");
+            let task2 = String::from("Task 2");
+            contract.add_tagged_task(task1.clone(), vec![String::from("outdoor")]);
+            contract.add_task(task2.clone());
+            contract.set_task_tags(
+                task2.clone(),
+                vec![String::from("outdoor"), String::from("coding")],
+            );
+
+            assert_eq!(
+                contract.get_tasks_by_tag(String::from("outdoor")),
+                vec![task1.clone(), task2.clone()]
+            );
+            assert_eq!(
+                contract.get_tasks_by_tag(String::from("coding")),
+                vec![task2.clone()]
+            );
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.remove_task(task2.clone());
+            assert_eq!(contract.get_task_tags(task2), Vec::<String>::new());
+            assert_eq!(
+                contract.get_tasks_by_tag(String::from("outdoor")),
+                vec![task1]
+            );
+        }
+
+        #[ink::test]
+        fn start_new_era_filters_by_category() {
+            let mut contract = create_default_contract();
+
+            contract.add_category(String::from("charity"));
+            contract.register_member();
+            for i in 0..10 {
+                contract.add_task(format!("Task {}", i));
+            }
+            let task = String::from("Charity task");
+            contract.add_tagged_task(task.clone(), vec![String::from("charity")]);
+
+            contract.set_era_categories(vec![String::from("charity")]);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            assert_eq!(contract.active_groups[0].task, task);
+        }
+
+        #[ink::test]
+        fn start_new_era_matches_skills() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for i in 0..10u8 {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.register_member();
+                if i % 2 == 0 {
+                    contract.set_skills(vec![String::from("rust"), String::from("hiking")]);
+                }
+            }
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.set_task_skills(task.clone(), vec![String::from("rust")]);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            assert_eq!(
+                contract.active_groups[0].participants.len(),
+                PARTICIPANTS_PER_ERA
+            );
+            for participant in &contract.active_groups[0].participants {
+                assert!(contract
+                    .get_skills(*participant)
+                    .contains(&String::from("rust")));
+            }
+        }
+
+        #[ink::test]
+        fn skill_redraw_respects_task_weights() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let diving = String::from("Diving task");
+            let funded = String::from("Funded task");
+            contract.add_task(diving.clone());
+            contract.set_task_skills(diving.clone(), vec![String::from("diving")]);
+            contract.add_task(String::from("Unfunded task"));
+            contract.add_task(funded.clone());
+            contract.set_task_recurrence(
+                funded.clone(),
+                Recurrence::Repeat {
+                    after_blocks: 0,
+                    max_occurrences: None,
+                },
+            );
+
+            set_balance(accounts.alice, 10_000);
+            ink::env::pay_with_call!(contract.fund_task(diving, None), 1_000);
+            contract.set_task_weighting(TaskWeighting::Bounty, None);
+
+            for _ in 0..3 {
+                ink::env::pay_with_call!(contract.fund_task(funded.clone(), None), 1);
+                advance_block(DEFAULT_SELECTION_ERA + 1);
+                contract.start_new_era();
+                // Nobody can dive, and the unfunded task weighs nothing
+                assert_eq!(contract.active_groups[0].task, funded);
+                contract.complete_task(0);
+            }
+        }
+
+        #[ink::test]
+        fn start_new_era_redraws_task_without_qualified_members() {
+            let mut contract = create_default_contract();
+
+            for i in 0..4u8 {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.register_member();
+            }
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            for i in 0..5 {
+                let task = format!("Task {}", i);
+                contract.add_task(task.clone());
+                contract.set_task_skills(task, vec![String::from("diving")]);
+            }
+            let task = String::from("Open task");
+            contract.add_task(task.clone());
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            assert_eq!(contract.active_groups[0].task, task);
+        }
+
+        #[ink::test]
+        fn start_new_era_falls_back_to_all_members() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.set_task_skills(task.clone(), vec![String::from("diving")]);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            assert_eq!(contract.active_groups[0].task, task);
+            assert_eq!(
+                contract.active_groups[0].participants.len(),
+                PARTICIPANTS_PER_ERA
+            );
+        }
+
+        #[ink::test]
+        fn availability_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.set_unavailable(5);
+
+            assert_eq!(contract.get_eligible_members(), vec![accounts.alice]);
+
+            advance_block(5);
+            assert_eq!(
+                contract.get_eligible_members(),
+                vec![accounts.alice, accounts.bob]
+            );
+
+            contract.set_unavailable(20);
+            contract.set_available();
+            assert_eq!(contract.get_eligible_members().len(), 2);
+            assert_eq!(test::recorded_events().count(), 5);
+        }
+
+        #[ink::test]
+        fn start_new_era_skips_unavailable_members() {
+            let mut contract = create_default_contract();
+
+            for i in 0..8u8 {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.register_member();
+                if i % 2 == 1 {
+                    contract.set_unavailable(100);
+                }
+            }
+            contract.add_task(String::from("Task"));
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            for participant in &contract.active_groups[0].participants {
+                assert_eq!(AsRef::<[u8]>::as_ref(participant)[0] % 2, 0);
+            }
+        }
+
+        // Registers `count` members with account ids `[0; 32]..[count - 1; 32]` and a
+        // single one-off task, leaving the caller set to alice.
+        fn setup_members_and_task(contract: &mut Polkapobal, count: u8) {
+            for i in 0..count {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.register_member();
+            }
+            contract.add_task(String::from("Task"));
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+        }
+
+        // Like `setup_members_and_task`, but the task is selectable again right after
+        // each completion, so the same members can be drawn across many eras
+        fn setup_members_and_recurring_task(contract: &mut Polkapobal, count: u8) {
+            setup_members_and_task(contract, count);
+            contract.set_task_recurrence(
+                String::from("Task"),
+                Recurrence::Repeat {
+                    after_blocks: 0,
+                    max_occurrences: None,
+                },
+            );
+        }
+
+        fn run_era(contract: &mut Polkapobal) -> Vec<AccountId> {
+            advance_block(contract.next_selection);
+            contract.start_new_era();
+            contract.complete_task(0);
+            contract.active_groups[0].participants.clone()
+        }
+
+        #[ink::test]
+        fn replacements_respect_selection_cooldown() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 8);
+            contract.set_selection_cooldown(2);
+            let first = run_era(&mut contract);
+
+            contract.set_acceptance_window(5);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let selected = contract.active_groups[0].participants.clone();
+            assert!(selected.iter().all(|member| !first.contains(member)));
+
+            // Members of the previous era are still cooling down in this one, so the
+            // slot of the decliner is left empty
+            ink::env::test::set_caller::<Environment>(selected[0]);
+            contract.decline_assignment(0);
+            let group = contract.active_groups[0].clone();
+            assert_eq!(group.participants.len(), 3);
+            assert!(group
+                .participants
+                .iter()
+                .all(|member| !first.contains(member)));
+        }
+
+        #[ink::test]
+        fn acceptance_step_replaces_participants() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 8);
+            contract.set_acceptance_window(5);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let selected = contract.active_groups[0].participants.clone();
+            assert_eq!(contract.active_groups[0].pending.len(), 4);
+            assert!(!contract.is_group_active(0));
+
+            ink::env::test::set_caller::<Environment>(selected[0]);
+            contract.accept_assignment(0);
+
+            // A decliner is replaced by a member outside the group, who must accept too
+            ink::env::test::set_caller::<Environment>(selected[1]);
+            contract.decline_assignment(0);
+            let group = contract.active_groups[0].clone();
+            assert!(!group.participants.contains(&selected[1]));
+            assert_eq!(group.participants.len(), 4);
+            let replacement = group.participants[3];
+            assert!(!selected.contains(&replacement));
+            assert!(group.pending.contains(&replacement));
+            assert_eq!(group.pending.len(), 3);
+
+            // Non-responders are replaced once the window closes
+            advance_block(5);
+            contract.close_acceptance();
+            let group = contract.active_groups[0].clone();
+            assert!(contract.is_group_active(0));
+            assert_eq!(group.participants.len(), 4);
+            assert_eq!(group.participants[0], selected[0]);
+            for member in &selected[1..] {
+                assert!(!group.participants.contains(member));
+            }
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
+        }
+
+        #[ink::test]
+        fn acceptance_step_rolls_back_selection_of_replaced_members() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 8);
+            run_era(&mut contract);
+            contract.set_acceptance_window(5);
+
+            let stats = |contract: &Polkapobal, member: AccountId| {
+                (
+                    contract.last_selected_era.get(member),
+                    contract.times_selected.get(member).unwrap_or(0),
+                )
+            };
+            let before: Vec<(AccountId, (Option<u32>, u32))> = contract
+                .members
+                .iter()
+                .map(|member| (*member, stats(&contract, *member)))
+                .collect();
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let selected = contract.active_groups[0].participants.clone();
+
+            ink::env::test::set_caller::<Environment>(selected[0]);
+            contract.accept_assignment(0);
+            ink::env::test::set_caller::<Environment>(selected[1]);
+            contract.decline_assignment(0);
+            advance_block(5);
+            contract.close_acceptance();
+
+            // Only the member who accepted keeps the selection
+            for (member, (last, times)) in before {
+                if member == selected[0] {
+                    assert_eq!(stats(&contract, member), (Some(2), times + 1));
+                } else if selected.contains(&member) {
+                    assert_eq!(stats(&contract, member), (last, times));
+                }
+            }
+        }
+
+        #[ink::test]
+        fn acceptance_step_activates_full_group() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 4);
+            contract.set_acceptance_window(5);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let selected = contract.active_groups[0].participants.clone();
+            for member in &selected {
+                ink::env::test::set_caller::<Environment>(*member);
+                contract.accept_assignment(0);
+            }
+
+            assert!(contract.is_group_active(0));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
+            assert_eq!(contract.active_groups[0].participants, selected);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Group not active")]
+        fn complete_task_before_acceptance_panics() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 4);
+            contract.set_acceptance_window(5);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            contract.complete_task(0);
+        }
+
+        #[ink::test]
+        fn fair_selection_covers_every_member_each_cycle() {
+            let mut contract = create_default_contract();
+            let num_members = 10u8;
+            setup_members_and_recurring_task(&mut contract, num_members);
+            contract.set_selection_strategy(SelectionStrategy::Fair);
+
+            let cycle = (num_members as usize).div_ceil(PARTICIPANTS_PER_ERA);
+            let eras: Vec<Vec<AccountId>> = (0..30).map(|_| run_era(&mut contract)).collect();
+
+            for window in eras.windows(cycle) {
+                for i in 0..num_members {
+                    let member = AccountId::from([i; 32]);
+                    assert!(window.iter().any(|era| era.contains(&member)));
+                }
+            }
+
+            // 30 eras of 4 participants spread over 10 members
+            for i in 0..num_members {
+                let member = AccountId::from([i; 32]);
+                let count = eras.iter().filter(|era| era.contains(&member)).count();
+                assert_eq!(count, 12);
+            }
+        }
+
+        #[ink::test]
+        fn social_mixing_avoids_repeat_pairings() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 8);
+            contract.set_selection_strategy(SelectionStrategy::SocialMixing);
+
+            let first = run_era(&mut contract);
+            let second = run_era(&mut contract);
+
+            for a in &second {
+                for b in &second {
+                    if a != b {
+                        assert!(!(first.contains(a) && first.contains(b)));
+                    }
+                }
+            }
+
+            // Every pair of the first group was grouped once
+            assert_eq!(contract.get_pair_count(first[0], first[1]), 1);
+        }
+
+        #[ink::test]
+        fn group_history_is_pruned() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 8);
+
+            for _ in 0..5 {
+                run_era(&mut contract);
+            }
+            assert_eq!(contract.group_history.len(), 5);
+
+            contract.set_group_history_limit(2);
+            assert_eq!(contract.group_history.len(), 2);
+
+            let last = run_era(&mut contract);
+            assert_eq!(contract.group_history.len(), 2);
+            assert_eq!(contract.group_history[1], last);
+        }
+
+        #[ink::test]
+        fn bounty_weighting_favours_funded_tasks() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            for i in 0..10 {
+                contract.add_task(format!("Task {}", i));
+            }
+            let funded = String::from("Task 3");
+            set_balance(accounts.alice, 1000);
+            ink::env::pay_with_call!(contract.fund_task(funded.clone(), None), 100);
+            contract.set_task_recurrence(
+                funded.clone(),
+                Recurrence::Repeat {
+                    after_blocks: 0,
+                    max_occurrences: None,
+                },
+            );
+
+            contract.set_task_weighting(TaskWeighting::Bounty, None);
+
+            let weights: Vec<u128> = contract
+                .tasks
+                .iter()
+                .map(|task| contract.task_weight(task))
+                .collect();
+            assert_eq!(weights.iter().sum::<u128>(), 100);
+
+            for _ in 0..5 {
+                advance_block(DEFAULT_SELECTION_ERA + 1);
+                contract.start_new_era();
+                assert_eq!(contract.active_groups[0].task, funded);
+                contract.complete_task(0);
+                // The bounty has been paid out, so fund the task again
+                ink::env::pay_with_call!(contract.fund_task(funded.clone(), None), 100);
+            }
+        }
+
+        #[ink::test]
+        fn bounty_weighting_picks_similarly_funded_tasks() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let tasks = [String::from("Task A"), String::from("Task B")];
+            for task in &tasks {
+                contract.add_task(task.clone());
+                contract.set_task_recurrence(
+                    task.clone(),
+                    Recurrence::Repeat {
+                        after_blocks: 0,
+                        max_occurrences: None,
+                    },
+                );
+            }
+            contract.set_task_weighting(TaskWeighting::Bounty, None);
+            set_balance(accounts.alice, 1_000_000);
+
+            // Both bounties are far above the block number
+            let mut picked: Vec<String> = Vec::new();
+            for _ in 0..20 {
+                for (task, bounty) in tasks.iter().zip([1_000, 1_200]) {
+                    let funds = contract.task_info.get(task).unwrap().1;
+                    if funds == 0 {
+                        ink::env::pay_with_call!(contract.fund_task(task.clone(), None), bounty);
+                    }
+                }
+                advance_block(DEFAULT_SELECTION_ERA + 1);
+                contract.start_new_era();
+                picked.push(contract.active_groups[0].task.clone());
+                contract.complete_task(0);
+            }
+
+            for task in &tasks {
+                assert!(picked.contains(task));
+            }
+        }
+
+        #[ink::test]
+        fn task_weight_cap_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            contract.add_task(String::from("Whale task"));
+            contract.add_task(String::from("Small task"));
+            set_balance(accounts.alice, 10_000);
+            ink::env::pay_with_call!(contract.fund_task(String::from("Whale task"), None), 9_000);
+            ink::env::pay_with_call!(contract.fund_task(String::from("Small task"), None), 10);
+
+            contract.set_task_weighting(TaskWeighting::Bounty, Some(10));
+
+            assert_eq!(contract.task_weight("Whale task"), 10);
+            assert_eq!(contract.task_weight("Small task"), 10);
+        }
+
+        #[ink::test]
+        fn age_weighting_boosts_waiting_tasks() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 4);
+            contract.set_task_weighting(TaskWeighting::Age, Some(3));
+
+            for _ in 0..5 {
+                run_era(&mut contract);
+            }
+            contract.add_task(String::from("New task"));
+
+            // The original task was just selected, the new one was just added
+            assert_eq!(contract.task_weight("Task"), 1);
+            assert_eq!(contract.task_weight("New task"), 1);
+
+            contract.era_count += 5;
+            assert_eq!(contract.task_weight("Task"), 3);
+        }
+
+        #[ink::test]
+        fn vote_task_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.vote_task(task.clone());
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.vote_task(task.clone());
+            assert_eq!(contract.get_task_votes(task.clone()), 2);
+
+            contract.unvote_task(task.clone());
+            assert_eq!(contract.get_task_votes(task.clone()), 1);
+            assert_eq!(test::recorded_events().count(), 6);
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.remove_task(task.clone());
+            assert_eq!(contract.get_task_votes(task), 0);
+            assert_eq!(contract.member_votes.get(accounts.alice), Some(vec![]));
+        }
+
+        #[ink::test]
+        fn completing_task_clears_member_votes() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 4);
+            let task = String::from("Task");
+            // Not selectable yet, so the era picks the voted task
+            contract.add_task(String::from("Other task"));
+            contract.set_task_window(String::from("Other task"), Some(1_000), None);
+
+            contract.vote_task(task.clone());
+            contract.vote_task(String::from("Other task"));
+            run_era(&mut contract);
+            assert_eq!(contract.get_task_votes(task.clone()), 0);
+            assert_eq!(
+                contract.member_votes.get(AccountId::from([1; 32])),
+                Some(vec![String::from("Other task")])
+            );
+
+            // The reopened task can be voted for again
+            contract.vote_task(task.clone());
+            assert_eq!(contract.get_task_votes(task), 1);
+        }
+
+        #[ink::test]
+        fn deregister_member_clears_votes() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.vote_task(task.clone());
+            assert_eq!(contract.get_task_votes(task.clone()), 1);
+
+            contract.deregister_member();
+            assert_eq!(contract.get_task_votes(task.clone()), 0);
+
+            // Registering again does not bring the vote back
+            contract.register_member();
+            assert_eq!(contract.get_task_votes(task.clone()), 0);
+            contract.vote_task(task.clone());
+            assert_eq!(contract.get_task_votes(task), 1);
+        }
+
+        #[ink::test]
+        fn vote_weighting_favours_voted_tasks() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            for i in 0..10 {
+                contract.add_task(format!("Task {}", i));
+            }
+            let voted = String::from("Task 7");
+            contract.vote_task(voted.clone());
+            contract.set_task_weighting(TaskWeighting::Votes, None);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            assert_eq!(contract.active_groups[0].task, voted);
+
+            // Votes are cleared once the task is completed
+            contract.complete_task(0);
+            assert_eq!(contract.get_task_votes(voted), 0);
+        }
+
+        #[ink::test]
+        fn start_new_era_starts_multiple_groups() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 10);
+            for i in 0..4 {
+                contract.add_task(format!("Task {}", i));
+            }
+            contract.set_groups_per_era(3);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            // Ten members only fill two groups of four
+            let groups = contract.get_active_groups();
+            assert_eq!(groups.len(), 2);
+            assert_ne!(groups[0].task, groups[1].task);
+            for member in &groups[0].participants {
+                assert!(!groups[1].participants.contains(member));
+            }
+
+            // Groups complete independently
+            contract.complete_task(1);
+            assert!(!contract.active_groups[0].is_complete);
+            assert!(contract.active_groups[1].is_complete);
+        }
+
+        #[ink::test]
+        fn group_without_participants_keeps_bounty() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            setup_members_and_task(&mut contract, 4);
+            let task = String::from("Task");
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 100);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            // Every participant leaves mid-era and nobody can replace them
+            for i in 0..4u8 {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.deregister_member();
+            }
+            assert!(contract.active_groups[0].participants.is_empty());
+            assert_eq!(contract.task_info.get(&task).unwrap(), (false, 100));
+
+            // The empty group does not hold up the next era, which pays the bounty out
+            let newcomers: Vec<AccountId> = (10..14u8).map(|i| AccountId::from([i; 32])).collect();
+            for member in &newcomers {
+                ink::env::test::set_caller::<Environment>(*member);
+                contract.register_member();
+                set_balance(*member, 0);
+            }
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            assert_eq!(contract.active_groups[0].task, task);
+            contract.complete_task(0);
+
+            let paid: Balance = newcomers.iter().map(|member| get_balance(*member)).sum();
+            assert_eq!(paid, 100);
+            assert_eq!(contract.unclaimed_funds, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Group has no participants")]
+        fn complete_task_without_participants_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            setup_members_and_task(&mut contract, 4);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            for i in 0..4u8 {
+                ink::env::test::set_caller::<Environment>(AccountId::from([i; 32]));
+                contract.deregister_member();
+            }
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
+        }
+
+        #[ink::test]
+        fn group_timeout_works() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 4);
+            contract.set_group_timeout(DEFAULT_SELECTION_ERA);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let events = test::recorded_events().count();
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            // `GroupTimedOut` and `NewEraStarted`
+            assert_eq!(test::recorded_events().count(), events + 2);
+            assert!(!contract.task_info.get(String::from("Task")).unwrap().0);
+        }
+
+        #[ink::test]
+        fn recurring_task_reopens() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            let task = String::from("Beach cleanup");
+            contract.add_task(task.clone());
+            contract.add_task(String::from("Other task"));
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.set_task_recurrence(
+                task.clone(),
+                Recurrence::Repeat {
+                    after_blocks: 100,
+                    max_occurrences: Some(2),
+                },
+            );
+            contract.set_selection_era(1);
+
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 40);
+
+            // Run eras until the task has been completed twice
+            let mut completions = 0;
+            let mut eras = 0;
+            while completions < 2 {
+                eras += 1;
+                assert!(eras <= 10, "Task was not selected twice");
+                advance_block(1);
+                contract.start_new_era();
+                let selected = contract.active_groups[0].task == task;
+                if selected {
+                    completions += 1;
+                    if completions == 1 {
+                        let (completed, funds) = contract.task_info.get(&task).unwrap();
+                        assert!(!completed);
+                        assert_eq!(funds, 40);
+                    }
+                }
+                contract.complete_task(0);
+
+                if selected && completions == 1 {
+                    // Reopened for funding, but not selectable before the delay
+                    assert_eq!(contract.task_info.get(&task).unwrap(), (false, 0));
+                    assert!(!contract.selectable_tasks().contains(&task));
+                    ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 8);
+                    advance_block(100);
+                }
+            }
+
+            assert_eq!(contract.get_task_occurrence_count(task.clone()), 2);
+            let first = contract.get_task_occurrence(task.clone(), 0).unwrap();
+            let second = contract.get_task_occurrence(task.clone(), 1).unwrap();
+            assert_eq!(first.funds, 40);
+            assert_eq!(second.funds, 8);
+            assert_eq!(first.participants, vec![accounts.bob]);
+            assert_eq!(contract.get_task_occurrence(task.clone(), 2), None);
+
+            // Both allowed runs are done
+            assert_eq!(contract.task_info.get(&task).unwrap(), (true, 0));
+        }
+
+        #[ink::test]
+        fn readded_task_starts_a_new_history() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 8);
+            let task = String::from("Task");
+
+            for _ in 0..3 {
+                run_era(&mut contract);
+            }
+            assert_eq!(contract.get_task_occurrence_count(task.clone()), 3);
+            assert!(contract.get_task_occurrence(task.clone(), 2).is_some());
+
+            contract.remove_task(task.clone());
+            contract.add_task(task.clone());
+            assert_eq!(contract.get_task_occurrence_count(task.clone()), 0);
+            assert_eq!(contract.get_task_occurrence(task.clone(), 0), None);
+
+            run_era(&mut contract);
+            assert_eq!(contract.get_task_occurrence_count(task.clone()), 1);
+            let occurrence = contract.get_task_occurrence(task.clone(), 0).unwrap();
+            assert_eq!(occurrence.era, contract.last_selection);
+            assert_eq!(contract.get_task_occurrence(task, 1), None);
+        }
+
+        #[ink::test]
+        fn task_window_limits_selection() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            contract.set_task_window(task.clone(), Some(10), Some(20));
+            assert_eq!(contract.get_task_window(task.clone()), (Some(10), Some(20)));
+
+            assert!(contract.selectable_tasks().is_empty());
+            advance_block(10);
+            assert_eq!(contract.selectable_tasks(), vec![task.clone()]);
+            advance_block(10);
+            assert!(contract.selectable_tasks().is_empty());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Task window is empty")]
+        fn set_empty_task_window_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+            contract.set_task_window(String::from("Task"), Some(20), Some(20));
+        }
+
+        #[ink::test]
+        fn sweep_expired_tasks_refunds_donors() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let expiring = String::from("Expiring task");
+            let lasting = String::from("Lasting task");
+            contract.add_task(expiring.clone());
+            contract.add_task(lasting.clone());
+            contract.set_task_window(expiring.clone(), None, Some(5));
+
+            set_balance(accounts.bob, 100);
+            set_balance(accounts.charlie, 100);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            ink::env::pay_with_call!(contract.fund_task(expiring.clone(), None), 10);
+            ink::env::pay_with_call!(contract.fund_task(expiring.clone(), None), 5);
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            ink::env::pay_with_call!(contract.fund_task(expiring.clone(), None), 20);
+            ink::env::pay_with_call!(contract.fund_task(lasting.clone(), None), 30);
+
+            // Nothing expired yet
+            assert_eq!(contract.sweep_expired_tasks(10), 0);
+            let round = contract.get_funding_round(expiring.clone()).unwrap();
+
+            // Anyone can sweep
+            advance_block(5);
+            ink::env::test::set_caller::<Environment>(accounts.django);
+            assert_eq!(contract.sweep_expired_tasks(10), 1);
+            assert_eq!(contract.tasks, vec![lasting.clone()]);
+            assert!(!contract.task_info.contains(&expiring));
+            assert_eq!(contract.unclaimed_funds, 0);
+
+            assert_eq!(contract.get_refund(round, accounts.bob), 15);
+            assert_eq!(contract.get_refund(round, accounts.charlie), 20);
+            assert_eq!(contract.get_funding_round(expiring), None);
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.claim_refund(round);
+            assert_eq!(get_balance(accounts.bob), 100);
+            assert_eq!(contract.get_refund(round, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn sweep_expired_tasks_examines_at_most_limit_tasks() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let lasting = String::from("Lasting task");
+            contract.add_task(lasting.clone());
+            for task in ["Expiring task 1", "Expiring task 2"] {
+                contract.add_task(String::from(task));
+                contract.set_task_window(String::from(task), None, Some(5));
+            }
+            advance_block(5);
+
+            // Each sweep picks up where the previous one stopped
+            assert_eq!(contract.sweep_expired_tasks(1), 0);
+            assert_eq!(contract.sweep_expired_tasks(1), 1);
+            assert_eq!(contract.sweep_expired_tasks(1), 1);
+            assert_eq!(contract.tasks, vec![lasting]);
+            assert_eq!(contract.sweep_expired_tasks(10), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Task expired")]
+        fn fund_expired_task_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.set_task_window(task.clone(), None, Some(5));
+
+            advance_block(5);
+            ink::env::pay_with_call!(contract.fund_task(task, None), 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Nothing to refund")]
+        fn claim_refund_without_refund_panics() {
+            let mut contract = create_default_contract();
+
+            contract.claim_refund(0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Nothing to refund")]
+        fn claim_refund_of_open_task_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 10);
+
+            contract.claim_refund(contract.get_funding_round(task).unwrap());
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Nothing to fund")]
+        fn fund_task_without_value_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            contract.fund_task(task, None);
+        }
+
+        #[ink::test]
+        fn completed_task_starts_a_new_funding_round() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 4);
+            let task = String::from("Task");
+
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 10);
+            let first = contract.get_funding_round(task.clone()).unwrap();
+            run_era(&mut contract);
+            assert_eq!(contract.get_funding_round(task.clone()), None);
+
+            // Donations paid out with the first run don't add up with later ones
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 5);
+            let second = contract.get_funding_round(task.clone()).unwrap();
+            assert_ne!(first, second);
+            assert_eq!(
+                contract
+                    .task_donations
+                    .get((second, AccountId::from([1; 32]))),
+                Some(5)
+            );
+            assert_eq!(contract.task_donated.get(&task), Some(5));
+        }
+
+        #[ink::test]
+        fn matching_round_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let popular = String::from("Popular task");
+            let whale = String::from("Whale task");
+            let unfunded = String::from("Unfunded task");
+            contract.add_task(popular.clone());
+            contract.add_task(whale.clone());
+            contract.add_task(unfunded.clone());
+
+            // Funds of a removed task become unclaimed
+            let removed = String::from("Removed task");
+            contract.add_task(removed.clone());
+            ink::env::pay_with_call!(contract.fund_task(removed.clone(), None), 100);
+            contract.remove_task(removed);
+            assert_eq!(contract.unclaimed_funds, 100);
+
+            ink::env::pay_with_call!(contract.open_matching_round(60), 40);
+            assert_eq!(contract.unclaimed_funds, 40);
+            assert_eq!(contract.matching_pool, 100);
+
+            // Four donors of 4 each vs a single donor of 16
+            for donor in [
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+                accounts.eve,
+            ] {
+                set_balance(donor, 100);
+                ink::env::test::set_caller::<Environment>(donor);
+                ink::env::pay_with_call!(contract.fund_task(popular.clone(), None), 4);
+            }
+            ink::env::test::set_caller::<Environment>(accounts.frank);
+            set_balance(accounts.frank, 100);
+            ink::env::pay_with_call!(contract.fund_task(whale.clone(), None), 16);
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.close_matching_round();
+
+            // Popular: (4 * 2)^2 - 16 = 48, whale: 4^2 - 16 = 0
+            assert_eq!(contract.task_info.get(&popular).unwrap(), (false, 16 + 100));
+            assert_eq!(contract.task_info.get(&whale).unwrap(), (false, 16));
+            assert_eq!(contract.task_info.get(&unfunded).unwrap(), (false, 0));
+            assert_eq!(contract.unclaimed_funds, 40);
+            assert!(contract.round_tasks.is_empty());
+            assert!(!contract.round_scores.contains(&popular));
+        }
+
+        #[ink::test]
+        fn matching_round_counts_only_its_own_contributions() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            set_balance(accounts.bob, 100);
+            set_balance(accounts.charlie, 100);
+
+            contract.open_matching_round(0);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 4);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.close_matching_round();
+
+            // Bob's donation of the first round is not added to his second one
+            contract.open_matching_round(0);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 4);
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), Some(5)), 9);
+            assert_eq!(contract.round_scores.get(&task), Some((2 + 3, 4 + 9)));
+
+            // A reclaimed pledge no longer counts
+            advance_block(5);
+            contract.reclaim_pledge(task.clone());
+            assert_eq!(contract.round_scores.get(&task), Some((2, 4)));
+            assert_eq!(contract.round_tasks, vec![task]);
+        }
+
+        #[ink::test]
+        fn matching_round_without_matches_returns_pool() {
+            let mut contract = create_default_contract();
+
+            ink::env::pay_with_call!(contract.open_matching_round(0), 30);
+            ink::env::pay_with_call!(contract.fund_matching_pool(), 20);
+            contract.close_matching_round();
+
+            assert_eq!(contract.unclaimed_funds, 50);
+            assert_eq!(contract.matching_pool, 0);
+        }
+
+        #[ink::test]
+        fn matching_round_splits_large_pool() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let first = String::from("First task");
+            let second = String::from("Second task");
+            contract.add_task(first.clone());
+            contract.add_task(second.clone());
+
+            // Large enough that `pool * score` overflows 128 bits
+            let pool: Balance = 1 << 100;
+            set_balance(accounts.alice, pool);
+            ink::env::pay_with_call!(contract.open_matching_round(0), pool);
+
+            let donation: Balance = 1 << 60;
+            for (donor, task) in [
+                (accounts.bob, &first),
+                (accounts.charlie, &first),
+                (accounts.django, &second),
+                (accounts.eve, &second),
+            ] {
+                set_balance(donor, donation);
+                ink::env::test::set_caller::<Environment>(donor);
+                ink::env::pay_with_call!(contract.fund_task(task.clone(), None), donation);
+            }
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.close_matching_round();
+
+            // Both tasks score the same, so each gets half of the pool
+            for task in [&first, &second] {
+                assert_eq!(
+                    contract.task_info.get(task).unwrap(),
+                    (false, 2 * donation + pool / 2)
+                );
+            }
+            assert_eq!(contract.unclaimed_funds, 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Not enough unclaimed funds")]
+        fn open_matching_round_above_unclaimed_funds_panics() {
+            let mut contract = create_default_contract();
+
+            contract.open_matching_round(1);
+        }
+
+        #[ink::test]
+        fn reclaim_pledge_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            set_balance(accounts.bob, 100);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), Some(10)), 30);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 5);
+            assert_eq!(
+                contract.get_pledge(task.clone(), accounts.bob),
+                Some((30, 10))
+            );
+            assert_eq!(contract.task_info.get(&task).unwrap(), (false, 35));
+
+            // Reclaiming works while paused
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.pause();
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+
+            advance_block(10);
+            contract.reclaim_pledge(task.clone());
+
+            assert_eq!(get_balance(accounts.bob), 100 - 5);
+            assert_eq!(contract.task_info.get(&task).unwrap(), (false, 5));
+            let round = contract.get_funding_round(task.clone()).unwrap();
+            assert_eq!(contract.task_donations.get((round, accounts.bob)), Some(5));
+            assert_eq!(contract.task_donated.get(&task), Some(5));
+            assert_eq!(contract.get_pledge(task, accounts.bob), None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Pledge deadline not reached")]
+        fn reclaim_pledge_before_deadline_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), Some(10)), 30);
+            advance_block(9);
+            contract.reclaim_pledge(task);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "No pledge to reclaim")]
+        fn reclaim_pledge_after_completion_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), Some(5)), 30);
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            contract.complete_task(0);
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.reclaim_pledge(task);
+        }
+
+        #[ink::test]
+        fn funding_goal_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.set_funding_goal(task.clone(), Some(30), Some(50));
+            assert_eq!(
+                contract.get_funding_goal(task.clone()),
+                (Some(30), Some(50))
+            );
+
+            // Goals only gate selection once required
+            assert_eq!(contract.selectable_tasks(), vec![task.clone()]);
+            contract.set_funding_goals_required(true);
+            assert!(contract.selectable_tasks().is_empty());
+
+            set_balance(accounts.bob, 100);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 20);
+            assert!(contract.selectable_tasks().is_empty());
+
+            let events_before = test::recorded_events().count();
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 10);
+            assert_eq!(contract.selectable_tasks(), vec![task.clone()]);
+            // TaskFunded and FundingGoalReached
+            assert_eq!(test::recorded_events().count(), events_before + 2);
+
+            // Contributions past the goal don't emit it again
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 20);
+            assert_eq!(test::recorded_events().count(), events_before + 3);
+            assert_eq!(contract.task_info.get(&task).unwrap(), (false, 50));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Funding cap exceeded")]
+        fn fund_task_above_cap_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.set_funding_goal(task.clone(), None, Some(10));
+
+            ink::env::pay_with_call!(contract.fund_task(task, None), 11);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Funding target above cap")]
+        fn set_funding_target_above_cap_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+            contract.set_funding_goal(String::from("Task"), Some(20), Some(10));
+        }
+
+        #[ink::test]
+        fn completed_task_is_not_selectable() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            contract.complete_task(0);
+
+            assert!(contract.selectable_tasks().is_empty());
+        }
+
+        #[ink::test]
+        fn selection_cooldown_works() {
+            let mut contract = create_default_contract();
+            setup_members_and_recurring_task(&mut contract, 12);
+            contract.set_selection_cooldown(3);
+
+            let eras: Vec<Vec<AccountId>> = (0..20).map(|_| run_era(&mut contract)).collect();
+
+            for (n, era) in eras.iter().enumerate() {
+                for member in era {
+                    for later in eras.iter().skip(n + 1).take(2) {
+                        assert!(!later.contains(member));
+                    }
+                }
+            }
+        }
+
+        #[ink::test]
+        fn task_review_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.set_task_review(true, 2);
+            contract.register_member();
+            let task1 = String::from("Task 1");
+            let task2 = String::from("Task 2");
+            contract.add_task(task1.clone());
+            contract.add_task(task2.clone());
+            assert!(contract.is_task_proposed(task1.clone()));
+            assert!(contract.selectable_tasks().is_empty());
+
+            // Curators approve right away
+            contract.approve_task(task1.clone());
+            assert!(!contract.is_task_proposed(task1.clone()));
+            assert_eq!(contract.selectable_tasks(), vec![task1]);
+
+            // Members approve once the threshold is met
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.approve_task(task2.clone());
+            assert!(contract.is_task_proposed(task2.clone()));
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+            contract.approve_task(task2.clone());
+            assert!(!contract.is_task_proposed(task2));
+        }
+
+        #[ink::test]
+        fn reject_task_works() {
+            let mut contract = create_default_contract();
+
+            contract.set_task_review(true, 0);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            contract.reject_task(task.clone(), Hash::from([0x01; 32]));
+
+            assert_eq!(contract.tasks.len(), 0);
+            assert!(!contract.task_info.contains(&task));
+            assert!(!contract.is_task_proposed(task));
+            assert_eq!(test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn remove_task_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.eve);
+
+            let mut contract = create_default_contract();
+            let contract_address = contract.env().account_id();
 
-            let task_info = self
-                .task_info
-                .take(&task)
-                .expect("Task existence verified before calling");
+            contract.register_member();
 
-            // If task is funded, add funds to unclaimed funds
-            self.unclaimed_funds = self
-                .unclaimed_funds
-                .checked_add(task_info.1)
-                .expect("Balance overflow");
+            let task1 = String::from("Task 1");
+            let task2 = String::from("Task 2");
+            contract.add_task(task1.clone());
+            contract.add_task(task2.clone());
 
-            // Search for index of member
-            let index = self
-                .tasks
-                .iter()
-                .position(|x| *x == task)
-                .expect("Task existence verified before calling");
-            self.tasks.swap_remove(index);
+            assert_eq!(contract.tasks.len(), 2);
+            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 0));
+            assert_eq!(contract.task_info.get(&task2).unwrap(), (false, 0));
 
-            self.env().emit_event(TaskRemoved { task: task });
-        }
+            set_balance(accounts.eve, 100);
+            set_balance(contract_address, 0);
 
-        #[ink(message)]
-        pub fn clear_tasks(&mut self) {
-            self.ensure_owner();
+            ink::env::pay_with_call!(contract.fund_task(task1.clone(), None), 10);
+            ink::env::pay_with_call!(contract.fund_task(task2.clone(), None), 20);
 
-            // Iterate over `active_tasks` vec and remove each member.
-            // Done in reverse so the task indices' do not change.
-            for (i, task) in self.tasks.clone().iter().enumerate().rev() {
-                let task_info = self
-                    .task_info
-                    .take(task)
-                    .expect("Task existence verified before calling");
-                self.unclaimed_funds = self
-                    .unclaimed_funds
-                    .checked_add(task_info.1)
-                    .expect("Balance overflow");
+            contract.remove_task(task1.clone());
 
-                self.tasks.swap_remove(i);
-            }
+            assert_eq!(contract.tasks.len(), 1);
+            assert_eq!(contract.task_info.get(&task1), None);
+            assert_eq!(contract.unclaimed_funds, 10);
+            assert_eq!(get_balance(contract_address), 30);
+
+            contract.remove_task(task2.clone());
+
+            assert_eq!(contract.tasks.len(), 0);
+            assert_eq!(contract.task_info.get(&task2), None);
+            assert_eq!(contract.unclaimed_funds, 30);
+            assert_eq!(get_balance(contract_address), 30);
 
-            self.env().emit_event(TasksCleared {});
+            assert_eq!(test::recorded_events().count(), 7);
         }
 
-        #[ink(message, payable)]
-        pub fn fund_task(&mut self, task: String) {
-            let caller = self.env().caller();
-            let transferred = self.env().transferred_value();
+        #[ink::test]
+        fn clear_tasks_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.eve);
 
-            //Ensure that the task does exist
-            assert!(self.task_info.contains(&task), "Task does not exist");
+            let mut contract = create_default_contract();
+            let contract_address = contract.env().account_id();
 
-            let mut task_info = self
-                .task_info
-                .get(&task)
-                .expect("Task existence verified before calling");
+            contract.register_member();
 
-            assert!(task_info.0 == false, "Task already completed");
+            let task1 = String::from("Task 1");
+            let task2 = String::from("Task 2");
+            let task3 = String::from("Task 3");
+            contract.add_task(task1.clone());
+            contract.add_task(task2.clone());
+            contract.add_task(task3.clone());
 
-            task_info.1 = task_info
-                .1
-                .checked_add(transferred)
-                .expect("Balance overflow");
+            set_balance(accounts.eve, 100);
+            set_balance(contract_address, 0);
 
-            self.task_info.insert(&task, &task_info);
+            ink::env::pay_with_call!(contract.fund_task(task1.clone(), None), 10);
+            ink::env::pay_with_call!(contract.fund_task(task2.clone(), None), 20);
 
-            self.env().emit_event(TaskFunded {
-                task: task,
-                donor: caller,
-                amount: transferred,
-            });
-        }
+            assert_eq!(contract.tasks.len(), 3);
 
-        #[ink(message)]
-        pub fn start_new_era(&mut self) {
-            self.ensure_era_reached();
-            // TODO: simplified logic for MVP
-            self.ensure_active_task_complete();
+            contract.clear_tasks();
 
-            let members = self.randomly_select_members();
-            let task = self.randomly_select_task();
+            assert_eq!(contract.tasks.len(), 0);
 
-            self.last_selection = self.env().block_number();
-            self.active_participants = members.clone();
-            self.active_task = Some((task.clone(), false));
+            assert!(!contract.task_info.contains(&task1));
+            assert!(!contract.task_info.contains(&task2));
+            assert!(!contract.task_info.contains(&task3));
+            assert_eq!(contract.unclaimed_funds, 30);
+            assert_eq!(get_balance(contract_address), 30);
 
-            self.env().emit_event(NewEraStarted {
-                era: Self::env().block_number(),
-                participants: members,
-                task: task,
-            });
+            assert_eq!(test::recorded_events().count(), 7);
         }
 
-        #[ink(message)]
-        pub fn upload_completion_proof(&mut self, proof: Hash) {
-            let caller = self.env().caller();
+        #[ink::test]
+        fn clear_tasks_batch_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.eve);
 
-            assert!(
-                self.active_participants.contains(&caller),
-                "Caller must be active participant"
-            );
+            let mut contract = create_default_contract();
+            let contract_address = contract.env().account_id();
 
-            if let Some(task) = &self.active_task {
-                self.proofs.insert(&task.0, &proof);
-            }
+            contract.register_member();
+
+            let task1 = String::from("Task 1");
+            let task2 = String::from("Task 2");
+            let task3 = String::from("Task 3");
+            contract.add_task(task1.clone());
+            contract.add_task(task2.clone());
+            contract.add_task(task3.clone());
+
+            set_balance(accounts.eve, 100);
+            set_balance(contract_address, 0);
+
+            ink::env::pay_with_call!(contract.fund_task(task1.clone(), None), 10);
+            ink::env::pay_with_call!(contract.fund_task(task3.clone(), None), 20);
+
+            assert_eq!(contract.clear_tasks_batch(1), 2);
+            assert!(contract.clearing_tasks);
+            assert!(!contract.task_info.contains(&task3));
+            assert_eq!(contract.unclaimed_funds, 20);
+            // `TasksCleared` is not emitted until the sweep finishes
+            assert_eq!(test::recorded_events().count(), 6);
+
+            assert_eq!(contract.clear_tasks_batch(5), 0);
+            assert!(!contract.clearing_tasks);
+            assert_eq!(contract.tasks.len(), 0);
+            assert!(!contract.task_info.contains(&task1));
+            assert!(!contract.task_info.contains(&task2));
+            assert_eq!(contract.unclaimed_funds, 30);
+            assert_eq!(test::recorded_events().count(), 7);
         }
 
-        #[ink(message)]
-        pub fn complete_task(&mut self) {
-            self.ensure_owner();
+        #[ink::test]
+        fn fund_task_works() {
+            let mut contract = create_default_contract();
 
-            if let Some(task) = &self.active_task {
-                self.task_info.insert(&task.0, &(true, 0));
-            }
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let contract_address = contract.env().account_id();
+
+            ink::env::test::set_caller::<Environment>(accounts.eve);
+
+            contract.register_member();
+
+            let task1 = String::from("Task 1");
+            contract.add_task(task1.clone());
+
+            assert_eq!(contract.tasks.len(), 1);
+            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 0));
+
+            set_balance(accounts.eve, 100);
+            set_balance(contract_address, 0);
+
+            ink::env::pay_with_call!(contract.fund_task(task1.clone(), None), 10);
+
+            assert_eq!(get_balance(contract_address), 10);
+            assert_eq!(get_balance(accounts.eve), 100 - 10);
+            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 10));
+
+            assert_eq!(test::recorded_events().count(), 3);
         }
 
-        fn randomly_select_members(&self) -> Vec<AccountId> {
-            // TODO: use randomness when chain extension is added
+        #[ink::test]
+        fn start_new_era_works() {
+            let init_block = 10;
+            advance_block(init_block);
 
-            self.ensure_non_empty_members();
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
             let mut members: Vec<AccountId> = Vec::new();
-            for i in 0..4 {
-                let member =
-                    self.members[(Self::env().block_number() as usize + i) % self.members.len()];
+
+            let num_members: u8 = 100;
+            // register 100 members with account ids 0..99
+            for i in 0..num_members {
+                let member = AccountId::from([i; 32]);
                 members.push(member);
+
+                ink::env::test::set_caller::<Environment>(member);
+                contract.register_member();
             }
 
-            members
-        }
+            ink::env::test::set_caller::<Environment>(accounts.alice);
 
-        fn randomly_select_task(&self) -> String {
-            self.ensure_non_empty_tasks();
+            let mut tasks: Vec<String> = Vec::new();
 
-            // TODO: use randomness
-            self.tasks[Self::env().block_number() as usize % self.tasks.len()].clone()
-        }
+            let num_tasks: u8 = 100;
+            // create 100 tasks
+            for i in 0..num_tasks {
+                let task = format!("Task {}", i);
+                tasks.push(task.clone());
 
-        fn ensure_owner(&self) {
-            assert_eq!(self.env().caller(), self.owner, "Only owner can call");
-        }
+                contract.add_task(task);
+            }
 
-        fn ensure_member(&self) {
-            assert!(
-                self.is_member.contains(&self.env().caller()),
-                "Must be a member to call"
+            assert_eq!(contract.members.len(), num_members as usize);
+            assert_eq!(contract.members, members);
+            assert_eq!(contract.last_selection, init_block);
+
+            // advance block to selection era
+            advance_block(DEFAULT_SELECTION_ERA);
+
+            contract.start_new_era();
+
+            assert_eq!(
+                contract.last_selection,
+                init_block + contract.next_selection
             );
-        }
+            assert_eq!(contract.active_groups[0].participants.len(), 4);
+            assert_eq!(contract.active_groups.len(), 1);
 
-        fn ensure_non_empty_members(&self) {
-            assert!(self.members.len() > 0, "Must have at least one member");
+            // TODO: add distribution tests when randomness is added
         }
 
-        fn ensure_non_empty_tasks(&self) {
-            assert!(self.tasks.len() > 0, "Must have at least one task");
-        }
+        #[ink::test]
+        fn accepted_tokens_work() {
+            let mut contract = create_default_contract();
+            let token = AccountId::from([0xAA; 32]);
 
-        fn ensure_active_task_complete(&self) {
-            if let Some(task) = &self.active_task {
-                assert!(task.1, "Active task must be completed");
-            }
-            // if None, simply return
-        }
+            contract.add_accepted_token(token);
+            assert!(contract.accepted_tokens.contains(token));
 
-        fn ensure_era_reached(&self) {
-            assert!(
-                self.env().block_number() >= self.last_selection + self.next_selection,
-                "Selection era not reached"
-            );
+            contract.remove_accepted_token(token);
+            assert!(!contract.accepted_tokens.contains(token));
+            assert_eq!(test::recorded_events().count(), 2);
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        /// Imports all the definitions from the outer scope so we can use them here.
-        use super::*;
-        use ink::codegen::Env;
-        use ink::env::test::{self};
+        #[ink::test]
+        fn complete_task_pays_out_participants() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract_address = contract.env().account_id();
 
-        const DEFAULT_SELECTION_ERA: u32 = 10;
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
 
-        fn create_default_contract() -> Polkapobal {
-            Polkapobal::new(DEFAULT_SELECTION_ERA)
-        }
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-        fn set_balance(account_id: AccountId, balance: Balance) {
-            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(account_id, balance)
+            set_balance(accounts.charlie, 100);
+            set_balance(contract_address, 0);
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 42);
+
+            set_balance(accounts.bob, 0);
+            set_balance(accounts.charlie, 0);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.start_new_era();
+            contract.complete_task(0);
+
+            // Two members fill four participant slots, so each gets two shares
+            assert_eq!(get_balance(accounts.bob), 20);
+            assert_eq!(get_balance(accounts.charlie), 20);
+            assert_eq!(contract.unclaimed_funds, 2);
+            assert_eq!(contract.task_info.get(&task).unwrap(), (true, 0));
         }
 
-        fn get_balance(account_id: AccountId) -> Balance {
-            ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account_id)
-                .expect("Cannot get account balance")
+        #[ink::test]
+        fn claim_payout_works() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // As left behind by a failed transfer in `pay_out`
+            contract.pending_payouts.insert(accounts.bob, &100);
+
+            set_balance(accounts.bob, 0);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.claim_payout();
+            assert_eq!(get_balance(accounts.bob), 100);
+            assert_eq!(contract.get_pending_payout(accounts.bob), 0);
         }
 
-        fn advance_block(num_blocks: u32) {
-            for _ in 0..num_blocks {
-                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
-            }
+        #[ink::test]
+        #[should_panic(expected = "Nothing to claim")]
+        fn claim_payout_without_pending_payout_panics() {
+            let mut contract = create_default_contract();
+
+            contract.claim_payout();
         }
 
         #[ink::test]
-        fn construction_works() {
-            let init_block = 5;
-            // non-zero block number to test
-            advance_block(init_block);
+        fn complete_task_twice_completes_once() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            let expected = Polkapobal {
-                owner: AccountId::from([0x01; 32]),
-                members: Vec::new(),
-                is_member: Mapping::default(),
-                tasks: Vec::new(),
-                task_info: Mapping::default(),
-                unclaimed_funds: 0,
-                start_block: init_block,
-                next_selection: DEFAULT_SELECTION_ERA,
-                last_selection: init_block,
-                active_participants: Vec::new(),
-                active_task: None,
-                proofs: Mapping::default(),
-            };
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.add_task(String::from("Task"));
 
-            let contract = Polkapobal::new(DEFAULT_SELECTION_ERA);
-            assert_eq!(contract.owner, expected.owner);
-            assert_eq!(contract.members.len(), 0);
-            assert_eq!(contract.tasks.len(), 0);
-            assert_eq!(contract.unclaimed_funds, expected.unclaimed_funds);
-            assert_eq!(contract.start_block, expected.start_block);
-            assert_eq!(contract.next_selection, expected.next_selection);
-            assert_eq!(contract.last_selection, expected.last_selection);
-            assert_eq!(contract.active_participants.len(), 0);
-            assert_eq!(contract.active_task, None);
+            advance_block(DEFAULT_SELECTION_ERA);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.start_new_era();
+            contract.complete_task(0);
+            // A second call neither pays out nor mints badges again
+            contract.complete_task(0);
+
+            assert_eq!(contract.badge_supply, 1);
+            assert_eq!(contract.badge_balances.get(accounts.bob), Some(1));
+            assert!(contract.active_groups[0].is_complete);
         }
 
         #[ink::test]
-        fn set_selection_era_works() {
+        fn keeper_is_rewarded() {
             let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            contract.set_selection_era(20);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            ink::env::pay_with_call!(contract.fund_task(task.clone(), None), 1_000);
 
-            assert_eq!(contract.next_selection, 20);
-            assert_eq!(test::recorded_events().count(), 1);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.set_keeper_reward(30, 200);
+            ink::env::pay_with_call!(contract.fund_keeper_pool(), 50);
+
+            set_balance(accounts.django, 0);
+            ink::env::test::set_caller::<Environment>(accounts.django);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            assert_eq!(get_balance(accounts.django), 30);
+            assert_eq!(contract.get_keeper_pool(), 20);
+
+            // 2% of the bounty refills the pool
+            set_balance(accounts.bob, 0);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
+            assert_eq!(contract.get_keeper_pool(), 40);
+            assert_eq!(get_balance(accounts.bob), 980);
         }
 
         #[ink::test]
-        fn register_member_works() {
+        fn keeper_is_rewarded_for_acceptance_and_timeouts() {
             let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 8);
+            contract.set_acceptance_window(5);
+            contract.set_group_timeout(DEFAULT_SELECTION_ERA);
+            contract.set_keeper_reward(10, 0);
+            ink::env::pay_with_call!(contract.fund_keeper_pool(), 100);
+
+            let keeper = AccountId::from([0xAA; 32]);
+            set_balance(keeper, 0);
+            ink::env::test::set_caller::<Environment>(keeper);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            assert_eq!(get_balance(keeper), 10);
 
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            // Closing the acceptance window is tipped once
+            advance_block(5);
+            contract.close_acceptance();
+            assert_eq!(get_balance(keeper), 20);
+            contract.close_acceptance();
+            assert_eq!(get_balance(keeper), 20);
 
-            contract.register_member();
+            // Timing out the group is part of starting the era, which is tipped once
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            assert_eq!(get_balance(keeper), 30);
+            assert_eq!(contract.get_keeper_pool(), 70);
+        }
 
-            ink::env::test::set_caller::<Environment>(accounts.bob);
-            contract.register_member();
+        #[ink::test]
+        #[should_panic(expected = "Keeper tip too high")]
+        fn set_keeper_tip_above_max_panics() {
+            let mut contract = create_default_contract();
 
-            assert_eq!(contract.members.len(), 2);
-            assert!(contract.members.contains(&accounts.alice));
-            assert!(contract.members.contains(&accounts.bob));
-            assert!(contract.is_member.contains(accounts.alice));
-            assert!(contract.is_member.contains(accounts.bob));
-            assert_eq!(test::recorded_events().count(), 2);
+            contract.set_keeper_reward(MAX_KEEPER_TIP + 1, 0);
         }
 
         #[ink::test]
-        fn deregister_member_works() {
+        #[should_panic(expected = "Keeper fee too high")]
+        fn set_keeper_fee_above_max_panics() {
             let mut contract = create_default_contract();
 
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.set_keeper_reward(0, MAX_KEEPER_FEE_BPS + 1);
+        }
 
-            contract.register_member();
+        #[ink::test]
+        fn complete_task_mints_badges() {
+            use crate::psp34::{PSP34Metadata, PSP34};
+
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
             ink::env::test::set_caller::<Environment>(accounts.bob);
             contract.register_member();
-
             ink::env::test::set_caller::<Environment>(accounts.charlie);
             contract.register_member();
 
-            assert_eq!(contract.members.len(), 3);
-            contract.deregister_member();
-            assert_eq!(contract.members.len(), 2);
-            assert!(!contract.members.contains(&accounts.charlie));
-            assert!(!contract.is_member.contains(&accounts.charlie));
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            ink::env::test::set_caller::<Environment>(accounts.bob);
-            contract.deregister_member();
-            assert_eq!(contract.members.len(), 1);
-            assert!(!contract.members.contains(&accounts.bob));
-            assert!(!contract.is_member.contains(&accounts.bob));
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            let proof = Hash::from([0x42; 32]);
+            contract.upload_completion_proof(proof, ProofKind::Photo, None);
 
             ink::env::test::set_caller::<Environment>(accounts.alice);
-            contract.deregister_member();
-            assert_eq!(contract.members.len(), 0);
-            assert!(!contract.members.contains(&accounts.alice));
-            assert!(!contract.is_member.contains(&accounts.alice));
-            assert_eq!(test::recorded_events().count(), 6);
+            contract.complete_task(0);
+
+            // Participants occupying several slots still get a single badge
+            assert_eq!(contract.total_supply(), 2);
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+            assert_eq!(contract.balance_of(accounts.charlie), 1);
+            assert_eq!(contract.balance_of(accounts.alice), 0);
+
+            let owner = contract.owner_of(Id::U64(0)).unwrap();
+            assert!(owner == accounts.bob || owner == accounts.charlie);
+            assert_eq!(
+                contract.get_attribute(Id::U64(0), b"task".to_vec()),
+                Some(b"Task".to_vec())
+            );
+            assert_eq!(
+                contract.get_attribute(Id::U64(0), b"era".to_vec()),
+                Some(DEFAULT_SELECTION_ERA.to_string().into_bytes())
+            );
+            assert_eq!(
+                contract.get_attribute(Id::U64(1), b"proof".to_vec()),
+                Some([0x42; 32].to_vec())
+            );
+            // The other participant did not upload a proof of their own
+            assert_eq!(contract.get_attribute(Id::U64(0), b"proof".to_vec()), None);
+
+            ink::env::test::set_caller::<Environment>(owner);
+            assert!(contract
+                .transfer(accounts.django, Id::U64(0), Vec::new())
+                .is_err());
+            assert_eq!(contract.owner_of(Id::U64(0)), Some(owner));
         }
 
         #[ink::test]
-        fn clear_members_works() {
+        fn upload_completion_proof_works() {
             let mut contract = create_default_contract();
-
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            contract.register_member();
-
             ink::env::test::set_caller::<Environment>(accounts.bob);
             contract.register_member();
-
             ink::env::test::set_caller::<Environment>(accounts.charlie);
             contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            assert_eq!(contract.members.len(), 3);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let era = contract.last_selection;
 
-            ink::env::test::set_caller::<Environment>(accounts.alice);
-            contract.clear_members();
+            ink::env::test::set_block_timestamp::<Environment>(1_000);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.upload_completion_proof(
+                Hash::from([1; 32]),
+                ProofKind::GitCommit,
+                Some(String::from("ipfs://cid")),
+            );
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.upload_completion_proof(Hash::from([2; 32]), ProofKind::Photo, None);
 
-            assert_eq!(contract.members.len(), 0);
-            assert!(!contract.is_member.contains(&accounts.alice));
-            assert!(!contract.is_member.contains(&accounts.bob));
-            assert!(!contract.is_member.contains(&accounts.charlie));
-            assert_eq!(test::recorded_events().count(), 4);
+            // Each participant keeps their own proof
+            assert_eq!(
+                contract.get_proof(era, accounts.bob),
+                Some(Proof {
+                    task: task.clone(),
+                    hash: Hash::from([1; 32]),
+                    uri: Some(String::from("ipfs://cid")),
+                    kind: ProofKind::GitCommit,
+                    timestamp: 1_000,
+                    approved: false,
+                })
+            );
+            assert_eq!(
+                contract.get_proof(era, accounts.charlie).unwrap().hash,
+                Hash::from([2; 32])
+            );
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
+            let occurrence = contract.get_task_occurrence(task, 0).unwrap();
+            assert_eq!(occurrence.proofs.len(), 2);
         }
 
         #[ink::test]
-        fn add_task_works() {
+        #[should_panic(expected = "Task already completed")]
+        fn upload_completion_proof_after_completion_panics() {
             let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
+            ink::env::test::set_caller::<Environment>(accounts.bob);
             contract.register_member();
+            contract.add_task(String::from("Task"));
 
-            let task1 = String::from("Task 1");
-            let task2 = String::from("Task 2");
-            contract.add_task(task1.clone());
-            contract.add_task(task2.clone());
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
 
-            assert_eq!(contract.tasks.len(), 2);
-            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 0));
-            assert_eq!(contract.task_info.get(&task2).unwrap(), (false, 0));
-            assert_eq!(test::recorded_events().count(), 3);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.upload_completion_proof(Hash::from([1; 32]), ProofKind::Photo, None);
         }
 
         #[ink::test]
-        fn remove_task_works() {
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_caller::<Environment>(accounts.eve);
-
+        fn approved_proofs_complete_task() {
             let mut contract = create_default_contract();
-            let contract_address = contract.env().account_id();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
+            ink::env::test::set_caller::<Environment>(accounts.bob);
             contract.register_member();
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            let task1 = String::from("Task 1");
-            let task2 = String::from("Task 2");
-            contract.add_task(task1.clone());
-            contract.add_task(task2.clone());
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.set_task_verifier(task.clone(), Some(accounts.frank));
+            assert_eq!(
+                contract.get_task_verifier(task.clone()),
+                Some(accounts.frank)
+            );
+            contract.set_proof_rule(task.clone(), ProofRule::PerParticipant);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let era = contract.last_selection;
 
-            assert_eq!(contract.tasks.len(), 2);
-            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 0));
-            assert_eq!(contract.task_info.get(&task2).unwrap(), (false, 0));
+            // The cross-contract `verify` call can't run off-chain, so verdicts are
+            // recorded directly
+            for member in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<Environment>(member);
+                let proof = Proof {
+                    task: task.clone(),
+                    hash: Hash::from([1; 32]),
+                    uri: None,
+                    kind: ProofKind::Attestation,
+                    timestamp: 0,
+                    approved: false,
+                };
+                contract.proofs.insert((era, member), &proof);
+            }
 
-            set_balance(accounts.eve, 100);
-            set_balance(contract_address, 0);
+            contract.record_verification(0, accounts.bob, true);
+            contract.record_verification(0, accounts.charlie, false);
+            assert!(!contract.active_groups[0].is_complete);
+            assert!(!contract.get_proof(era, accounts.charlie).unwrap().approved);
 
-            ink::env::pay_with_call!(contract.fund_task(task1.clone()), 10);
-            ink::env::pay_with_call!(contract.fund_task(task2.clone()), 20);
+            contract.record_verification(0, accounts.charlie, true);
+            assert!(contract.active_groups[0].is_complete);
+            assert_eq!(contract.task_info.get(&task).unwrap(), (true, 0));
+        }
 
-            contract.remove_task(task1.clone());
+        // Signs `message_hash` like an oracle holding `secret` would.
+        fn sign_attestation(secret: [u8; 32], message_hash: [u8; 32]) -> [u8; 65] {
+            use secp256k1::{Message, SecretKey, SECP256K1};
 
-            assert_eq!(contract.tasks.len(), 1);
-            assert_eq!(contract.task_info.get(&task1), None);
-            assert_eq!(contract.unclaimed_funds, 10);
-            assert_eq!(get_balance(contract_address), 30);
+            let secret = SecretKey::from_slice(&secret).unwrap();
+            let message = Message::from_slice(&message_hash).unwrap();
+            let (recovery_id, compact) = SECP256K1
+                .sign_ecdsa_recoverable(&message, &secret)
+                .serialize_compact();
 
-            contract.remove_task(task2.clone());
+            let mut signature = [0u8; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            signature
+        }
 
-            assert_eq!(contract.tasks.len(), 0);
-            assert_eq!(contract.task_info.get(&task2), None);
-            assert_eq!(contract.unclaimed_funds, 30);
-            assert_eq!(get_balance(contract_address), 30);
+        fn oracle_key(secret: [u8; 32]) -> OracleKey {
+            use secp256k1::{PublicKey, SecretKey, SECP256K1};
 
-            assert_eq!(test::recorded_events().count(), 7);
+            let secret = SecretKey::from_slice(&secret).unwrap();
+            PublicKey::from_secret_key(SECP256K1, &secret).serialize()
         }
 
         #[ink::test]
-        fn clear_tasks_works() {
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_caller::<Environment>(accounts.eve);
-
+        fn submit_attestation_works() {
             let mut contract = create_default_contract();
-            let contract_address = contract.env().account_id();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let secret = [0x42; 32];
 
+            ink::env::test::set_caller::<Environment>(accounts.bob);
             contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            let task1 = String::from("Task 1");
-            let task2 = String::from("Task 2");
-            let task3 = String::from("Task 3");
-            contract.add_task(task1.clone());
-            contract.add_task(task2.clone());
-            contract.add_task(task3.clone());
-
-            set_balance(accounts.eve, 100);
-            set_balance(contract_address, 0);
-
-            ink::env::pay_with_call!(contract.fund_task(task1.clone()), 10);
-            ink::env::pay_with_call!(contract.fund_task(task2.clone()), 20);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.add_oracle(oracle_key(secret));
+            contract.set_task_oracles(task.clone(), vec![oracle_key(secret)]);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+            let era = contract.last_selection;
 
-            assert_eq!(contract.tasks.len(), 3);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            let message_hash = contract.attestation_hash(&task, accounts.bob, 0);
+            contract.submit_attestation(sign_attestation(secret, message_hash));
+
+            let proof = contract.get_proof(era, accounts.bob).unwrap();
+            assert!(proof.approved);
+            assert_eq!(proof.kind, ProofKind::Attestation);
+            assert_eq!(contract.get_attestation_nonce(accounts.bob), 1);
+            // The approved proof completes the group
+            assert!(contract.active_groups[0].is_complete);
+        }
 
-            contract.clear_tasks();
+        #[ink::test]
+        #[should_panic(expected = "Unknown oracle")]
+        fn submit_attestation_from_unknown_oracle_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            assert_eq!(contract.tasks.len(), 0);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            assert!(!contract.task_info.contains(&task1));
-            assert!(!contract.task_info.contains(&task2));
-            assert!(!contract.task_info.contains(&task3));
-            assert_eq!(contract.unclaimed_funds, 30);
-            assert_eq!(get_balance(contract_address), 30);
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.add_oracle(oracle_key([0x42; 32]));
+            contract.set_task_oracles(task.clone(), vec![oracle_key([0x42; 32])]);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
 
-            assert_eq!(test::recorded_events().count(), 7);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            let message_hash = contract.attestation_hash(&task, accounts.bob, 0);
+            contract.submit_attestation(sign_attestation([0x43; 32], message_hash));
         }
 
         #[ink::test]
-        fn fund_task_works() {
+        #[should_panic(expected = "Unknown oracle")]
+        fn submit_attestation_from_oracle_of_other_task_panics() {
             let mut contract = create_default_contract();
-
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            let contract_address = contract.env().account_id();
-
-            ink::env::test::set_caller::<Environment>(accounts.eve);
-
+            ink::env::test::set_caller::<Environment>(accounts.bob);
             contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            let task1 = String::from("Task 1");
-            contract.add_task(task1.clone());
+            // Both oracles are registered, but only the first attests the task
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.add_oracle(oracle_key([0x42; 32]));
+            contract.add_oracle(oracle_key([0x43; 32]));
+            contract.set_task_oracles(task.clone(), vec![oracle_key([0x42; 32])]);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
 
-            assert_eq!(contract.tasks.len(), 1);
-            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 0));
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            let message_hash = contract.attestation_hash(&task, accounts.bob, 0);
+            contract.submit_attestation(sign_attestation([0x43; 32], message_hash));
+        }
 
-            set_balance(accounts.eve, 100);
-            set_balance(contract_address, 0);
+        #[ink::test]
+        #[should_panic(expected = "Task takes no attestations")]
+        fn submit_attestation_for_task_without_oracle_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let secret = [0x42; 32];
 
-            ink::env::pay_with_call!(contract.fund_task(task1.clone()), 10);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            assert_eq!(get_balance(contract_address), 10);
-            assert_eq!(get_balance(accounts.eve), 100 - 10);
-            assert_eq!(contract.task_info.get(&task1).unwrap(), (false, 10));
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.add_oracle(oracle_key(secret));
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
 
-            assert_eq!(test::recorded_events().count(), 3);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            let message_hash = contract.attestation_hash(&task, accounts.bob, 0);
+            contract.submit_attestation(sign_attestation(secret, message_hash));
         }
 
         #[ink::test]
-        fn start_new_era_works() {
-            let init_block = 10;
-            advance_block(init_block);
-
+        #[should_panic(expected = "Unknown oracle")]
+        fn set_unregistered_task_oracle_panics() {
             let mut contract = create_default_contract();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            let mut members: Vec<AccountId> = Vec::new();
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+            contract.set_task_oracles(task, vec![oracle_key([0x42; 32])]);
+        }
 
-            let num_members: u8 = 100;
-            // register 100 members with account ids 0..99
-            for i in 0..num_members {
-                let member = AccountId::from([i; 32]);
-                members.push(member);
+        #[ink::test]
+        #[should_panic(expected = "Unknown oracle")]
+        fn replayed_attestation_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let secret = [0x42; 32];
 
-                ink::env::test::set_caller::<Environment>(member);
-                contract.register_member();
-            }
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
             ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.add_oracle(oracle_key(secret));
+            contract.set_task_oracles(task.clone(), vec![oracle_key(secret)]);
+            contract.set_proof_rule(task.clone(), ProofRule::PerParticipant);
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
 
-            let mut tasks: Vec<String> = Vec::new();
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            let message_hash = contract.attestation_hash(&task, accounts.bob, 0);
+            let signature = sign_attestation(secret, message_hash);
+            contract.submit_attestation(signature);
 
-            let num_tasks: u8 = 100;
-            // create 100 tasks
-            for i in 0..num_tasks {
-                let task = String::from(format!("Task {}", i));
-                tasks.push(task.clone());
+            // The nonce moved on, so the old signature recovers a different key
+            contract.submit_attestation(signature);
+        }
 
-                contract.add_task(task);
-            }
+        #[ink::test]
+        #[should_panic(expected = "Missing completion proof")]
+        fn complete_task_without_required_proofs_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            assert_eq!(contract.members.len(), num_members as usize);
-            assert_eq!(contract.members, members);
-            assert_eq!(contract.last_selection, init_block);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
 
-            // advance block to selection era
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.set_proof_rule(task, ProofRule::PerParticipant);
             advance_block(DEFAULT_SELECTION_ERA);
-
             contract.start_new_era();
 
-            assert_eq!(
-                contract.last_selection,
-                init_block + contract.next_selection
-            );
-            assert_eq!(contract.active_participants.len(), 4);
-            assert_eq!(contract.active_task.is_some(), true);
+            // One proof satisfies a group rule, but not a per-participant one
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.upload_completion_proof(Hash::from([1; 32]), ProofKind::Photo, None);
 
-            // TODO: add distribution tests when randomness is added
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.complete_task(0);
         }
 
-        // TODO: unit tests for:
-        // - upload_completion_proof
-        // - complete_task
-        // - start_new_era passes and panics when task complete and not complete, respectively
-
         #[ink::test]
         #[should_panic(expected = "Only owner can call")]
         fn set_selection_era_panics() {
@@ -724,6 +6377,18 @@ mod polkapobal {
             contract.clear_members();
         }
 
+        #[ink::test]
+        #[should_panic(expected = "Active task must be completed")]
+        fn clear_members_with_incomplete_group_panics() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 4);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            contract.clear_members_batch(2);
+        }
+
         #[ink::test]
         #[should_panic(expected = "Task already exists")]
         fn add_task_twice_panics() {
@@ -790,6 +6455,239 @@ mod polkapobal {
             contract.clear_tasks();
         }
 
+        #[ink::test]
+        #[should_panic(expected = "Members are being cleared")]
+        fn register_member_while_clearing_members_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+
+            ink::env::test::set_caller::<Environment>(accounts.alice);
+            contract.clear_members_batch(1);
+
+            ink::env::test::set_caller::<Environment>(accounts.charlie);
+            contract.register_member();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Tasks are being cleared")]
+        fn add_task_while_clearing_tasks_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            contract.add_task(String::from("Task 1"));
+            contract.add_task(String::from("Task 2"));
+
+            contract.clear_tasks_batch(1);
+
+            contract.add_task(String::from("Task 3"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only owner or pauser can call")]
+        fn pause_when_not_pauser_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.eve);
+
+            contract.pause();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Operation is paused")]
+        fn fund_task_when_paused_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            contract.pause();
+
+            set_balance(accounts.alice, 100);
+            ink::env::pay_with_call!(contract.fund_task(task, None), 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Operation is paused")]
+        fn start_new_era_when_eras_paused_panics() {
+            let mut contract = create_default_contract();
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+
+            contract.pause_category(PauseCategory::Eras);
+            advance_block(DEFAULT_SELECTION_ERA);
+
+            contract.start_new_era();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Token not accepted")]
+        fn fund_task_with_unaccepted_token_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            contract.fund_task_with_token(task, AccountId::from([0xAA; 32]), 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Category does not exist")]
+        fn tag_with_unknown_category_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            contract.add_tagged_task(String::from("Task"), vec![String::from("outdoor")]);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Too many tags")]
+        fn too_many_tags_panics() {
+            let mut contract = create_default_contract();
+
+            let mut tags = Vec::new();
+            for i in 0..=MAX_TAGS_PER_TASK {
+                let tag = format!("Tag {}", i);
+                contract.add_category(tag.clone());
+                tags.push(tag);
+            }
+
+            contract.register_member();
+            contract.add_tagged_task(String::from("Task"), tags);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only owner or curator can call")]
+        fn add_category_when_not_curator_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<Environment>(accounts.eve);
+
+            contract.add_category(String::from("outdoor"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "No task is selectable")]
+        fn start_new_era_without_matching_task_panics() {
+            let mut contract = create_default_contract();
+
+            contract.add_category(String::from("charity"));
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+            contract.set_era_categories(vec![String::from("charity")]);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Must be a member to call")]
+        fn set_skills_when_not_member_panics() {
+            let mut contract = create_default_contract();
+
+            contract.set_skills(vec![String::from("rust")]);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "No member is available")]
+        fn start_new_era_without_available_members_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+            contract.set_unavailable(100);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Already voted for task")]
+        fn vote_task_twice_panics() {
+            let mut contract = create_default_contract();
+
+            contract.register_member();
+            let task = String::from("Task");
+            contract.add_task(task.clone());
+
+            contract.vote_task(task.clone());
+            contract.vote_task(task);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "No task is selectable")]
+        fn start_new_era_with_only_proposed_tasks_panics() {
+            let mut contract = create_default_contract();
+
+            contract.set_task_review(true, 0);
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Cannot approve own task")]
+        fn approve_own_task_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.set_task_review(true, 1);
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+            contract.approve_task(String::from("Task"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Task not approved")]
+        fn fund_proposed_task_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.set_task_review(true, 0);
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+
+            set_balance(accounts.alice, 100);
+            ink::env::pay_with_call!(contract.fund_task(String::from("Task"), None), 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only owner or curator can call")]
+        fn approve_task_without_member_approvals_panics() {
+            let mut contract = create_default_contract();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.set_task_review(true, 0);
+            contract.register_member();
+            contract.add_task(String::from("Task"));
+
+            ink::env::test::set_caller::<Environment>(accounts.bob);
+            contract.register_member();
+            contract.approve_task(String::from("Task"));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Active task must be completed")]
+        fn start_new_era_with_incomplete_group_panics() {
+            let mut contract = create_default_contract();
+            setup_members_and_task(&mut contract, 4);
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+
+            advance_block(DEFAULT_SELECTION_ERA);
+            contract.start_new_era();
+        }
+
         //TODO: test for panic if task is already completed
 
         #[ink::test]
@@ -807,7 +6705,7 @@ mod polkapobal {
 
             let task = String::from("Task");
             // task does not exist
-            ink::env::pay_with_call!(contract.fund_task(task), 10);
+            ink::env::pay_with_call!(contract.fund_task(task, None), 10);
         }
 
         #[ink::test]
@@ -841,4 +6739,286 @@ mod polkapobal {
             contract.start_new_era();
         }
     }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::build_message;
+        use psp22_mock::{Psp22MockRef, PSP22};
+        use verifier_mock::VerifierMockRef;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        const TASK: &str = "Task";
+        const BOUNTY: Balance = 400;
+
+        // Deploys the contract and a token, lets bob register and add `TASK`, and
+        // charlie fund it with `BOUNTY` tokens. Returns `(contract, token)`.
+        async fn setup_token_bounty(
+            client: &mut ink_e2e::Client<ink_e2e::PolkadotConfig, ink::env::DefaultEnvironment>,
+        ) -> (AccountId, AccountId) {
+            let token = client
+                .instantiate(
+                    "psp22_mock",
+                    &ink_e2e::charlie(),
+                    Psp22MockRef::new(1_000),
+                    0,
+                    None,
+                )
+                .await
+                .expect("token instantiate failed")
+                .account_id;
+            let contract = client
+                .instantiate(
+                    "polkapobal",
+                    &ink_e2e::alice(),
+                    PolkapobalRef::new(0),
+                    0,
+                    None,
+                )
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let register = build_message::<PolkapobalRef>(contract).call(|c| c.register_member());
+            client
+                .call(&ink_e2e::bob(), register, 0, None)
+                .await
+                .expect("register_member failed");
+            let add_task =
+                build_message::<PolkapobalRef>(contract).call(|c| c.add_task(String::from(TASK)));
+            client
+                .call(&ink_e2e::bob(), add_task, 0, None)
+                .await
+                .expect("add_task failed");
+
+            let accept =
+                build_message::<PolkapobalRef>(contract).call(|c| c.add_accepted_token(token));
+            client
+                .call(&ink_e2e::alice(), accept, 0, None)
+                .await
+                .expect("add_accepted_token failed");
+
+            let approve =
+                build_message::<Psp22MockRef>(token).call(|t| t.approve(contract, BOUNTY));
+            client
+                .call(&ink_e2e::charlie(), approve, 0, None)
+                .await
+                .expect("approve failed");
+            let fund = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.fund_task_with_token(String::from(TASK), token, BOUNTY));
+            client
+                .call(&ink_e2e::charlie(), fund, 0, None)
+                .await
+                .expect("fund_task_with_token failed");
+
+            (contract, token)
+        }
+
+        async fn run_era(
+            client: &mut ink_e2e::Client<ink_e2e::PolkadotConfig, ink::env::DefaultEnvironment>,
+            contract: AccountId,
+        ) {
+            let start = build_message::<PolkapobalRef>(contract).call(|c| c.start_new_era());
+            client
+                .call(&ink_e2e::alice(), start, 0, None)
+                .await
+                .expect("start_new_era failed");
+            let complete = build_message::<PolkapobalRef>(contract).call(|c| c.complete_task(0));
+            client
+                .call(&ink_e2e::alice(), complete, 0, None)
+                .await
+                .expect("complete_task failed");
+        }
+
+        #[ink_e2e::test(additional_contracts = "mocks/psp22_mock/Cargo.toml")]
+        async fn token_bounty_is_paid_out(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let (contract, token) = setup_token_bounty(&mut client).await;
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            run_era(&mut client, contract).await;
+
+            // Bob fills every slot, so the whole bounty goes to him
+            let balance = build_message::<Psp22MockRef>(token).call(|t| t.balance_of(bob));
+            let balance = client
+                .call_dry_run(&ink_e2e::alice(), &balance, 0, None)
+                .await
+                .return_value();
+            assert_eq!(balance, BOUNTY);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "mocks/psp22_mock/Cargo.toml")]
+        async fn expired_token_bounty_is_refunded(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let (contract, token) = setup_token_bounty(&mut client).await;
+            let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+
+            let expire = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.set_task_window(String::from(TASK), None, Some(1)));
+            client
+                .call(&ink_e2e::alice(), expire, 0, None)
+                .await
+                .expect("set_task_window failed");
+            let round = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.get_funding_round(String::from(TASK)));
+            let round = client
+                .call_dry_run(&ink_e2e::alice(), &round, 0, None)
+                .await
+                .return_value()
+                .expect("Task not funded");
+            let sweep =
+                build_message::<PolkapobalRef>(contract).call(|c| c.sweep_expired_tasks(10));
+            client
+                .call(&ink_e2e::bob(), sweep, 0, None)
+                .await
+                .expect("sweep_expired_tasks failed");
+
+            let claim = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.claim_token_refund(round, token));
+            client
+                .call(&ink_e2e::charlie(), claim, 0, None)
+                .await
+                .expect("claim_token_refund failed");
+
+            let balance = build_message::<Psp22MockRef>(token).call(|t| t.balance_of(charlie));
+            let balance = client
+                .call_dry_run(&ink_e2e::alice(), &balance, 0, None)
+                .await
+                .return_value();
+            assert_eq!(balance, 1_000);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "mocks/verifier_mock/Cargo.toml")]
+        async fn verifier_rejects_and_accepts_proofs(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let valid_proof = Hash::from([0x42; 32]);
+            let verifier = client
+                .instantiate(
+                    "verifier_mock",
+                    &ink_e2e::alice(),
+                    VerifierMockRef::new(valid_proof),
+                    0,
+                    None,
+                )
+                .await
+                .expect("verifier instantiate failed")
+                .account_id;
+            let contract = client
+                .instantiate(
+                    "polkapobal",
+                    &ink_e2e::alice(),
+                    PolkapobalRef::new(0),
+                    0,
+                    None,
+                )
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let register = build_message::<PolkapobalRef>(contract).call(|c| c.register_member());
+            client
+                .call(&ink_e2e::bob(), register, 0, None)
+                .await
+                .expect("register_member failed");
+            let add_task =
+                build_message::<PolkapobalRef>(contract).call(|c| c.add_task(String::from(TASK)));
+            client
+                .call(&ink_e2e::bob(), add_task, 0, None)
+                .await
+                .expect("add_task failed");
+            let set_verifier = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.set_task_verifier(String::from(TASK), Some(verifier)));
+            client
+                .call(&ink_e2e::alice(), set_verifier, 0, None)
+                .await
+                .expect("set_task_verifier failed");
+            let start = build_message::<PolkapobalRef>(contract).call(|c| c.start_new_era());
+            client
+                .call(&ink_e2e::alice(), start, 0, None)
+                .await
+                .expect("start_new_era failed");
+
+            // A rejected proof leaves the group running
+            let upload = build_message::<PolkapobalRef>(contract).call(|c| {
+                c.upload_completion_proof(Hash::from([0x01; 32]), ProofKind::Photo, None)
+            });
+            client
+                .call(&ink_e2e::bob(), upload, 0, None)
+                .await
+                .expect("upload_completion_proof failed");
+            let groups = build_message::<PolkapobalRef>(contract).call(|c| c.get_active_groups());
+            let groups = client
+                .call_dry_run(&ink_e2e::alice(), &groups, 0, None)
+                .await
+                .return_value();
+            assert!(!groups[0].is_complete);
+
+            // An accepted proof completes it
+            let upload = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.upload_completion_proof(valid_proof, ProofKind::Photo, None));
+            client
+                .call(&ink_e2e::bob(), upload, 0, None)
+                .await
+                .expect("upload_completion_proof failed");
+            let groups = build_message::<PolkapobalRef>(contract).call(|c| c.get_active_groups());
+            let groups = client
+                .call_dry_run(&ink_e2e::alice(), &groups, 0, None)
+                .await
+                .return_value();
+            assert!(groups[0].is_complete);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "mocks/psp22_mock/Cargo.toml")]
+        async fn failing_token_payout_can_be_claimed(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let (contract, token) = setup_token_bounty(&mut client).await;
+
+            let fail = build_message::<Psp22MockRef>(token).call(|t| t.set_failing(true));
+            client
+                .call(&ink_e2e::charlie(), fail, 0, None)
+                .await
+                .expect("set_failing failed");
+
+            run_era(&mut client, contract).await;
+
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let pending = build_message::<PolkapobalRef>(contract)
+                .call(|c| c.get_pending_token_payout(bob, token));
+            let pending = client
+                .call_dry_run(&ink_e2e::alice(), &pending, 0, None)
+                .await
+                .return_value();
+            assert_eq!(pending, BOUNTY);
+
+            // Bob claims his shares once the token works again
+            let fix = build_message::<Psp22MockRef>(token).call(|t| t.set_failing(false));
+            client
+                .call(&ink_e2e::charlie(), fix, 0, None)
+                .await
+                .expect("set_failing failed");
+            let claim =
+                build_message::<PolkapobalRef>(contract).call(|c| c.claim_token_payout(token));
+            client
+                .call(&ink_e2e::bob(), claim, 0, None)
+                .await
+                .expect("claim_token_payout failed");
+            let balance = build_message::<Psp22MockRef>(token).call(|t| t.balance_of(bob));
+            let balance = client
+                .call_dry_run(&ink_e2e::alice(), &balance, 0, None)
+                .await
+                .return_value();
+            assert_eq!(balance, BOUNTY);
+
+            Ok(())
+        }
+    }
 }
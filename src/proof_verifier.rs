@@ -0,0 +1,39 @@
+//! Interface of contracts that check completion proofs automatically.
+
+use ink::{
+    env::{
+        call::{build_call, ExecutionInput, Selector},
+        DefaultEnvironment,
+    },
+    prelude::string::String,
+    primitives::{AccountId, Hash},
+};
+
+/// Gas a verifier may use per proof, so a misbehaving one cannot use up the
+/// upload's whole gas.
+pub const VERIFY_GAS_LIMIT: u64 = 5_000_000_000;
+
+#[ink::trait_definition]
+pub trait ProofVerifier {
+    /// Whether `proof` shows that `participant` completed `task_id`.
+    #[ink(message)]
+    fn verify(&self, task_id: String, participant: AccountId, proof: Hash) -> bool;
+}
+
+/// Calls `ProofVerifier::verify` on `verifier`. A failing call, including one that
+/// runs out of `VERIFY_GAS_LIMIT`, counts as a rejection.
+pub fn verify(verifier: AccountId, task_id: String, participant: AccountId, proof: Hash) -> bool {
+    let result = build_call::<DefaultEnvironment>()
+        .call(verifier)
+        .gas_limit(VERIFY_GAS_LIMIT)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("ProofVerifier::verify")))
+                .push_arg(task_id)
+                .push_arg(participant)
+                .push_arg(proof),
+        )
+        .returns::<bool>()
+        .try_invoke();
+
+    matches!(result, Ok(Ok(true)))
+}
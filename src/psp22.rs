@@ -0,0 +1,67 @@
+//! Minimal cross-contract bindings for PSP22 token contracts.
+
+use ink::{
+    env::{
+        call::{build_call, ExecutionInput, Selector},
+        DefaultEnvironment,
+    },
+    prelude::{string::String, vec::Vec},
+    primitives::AccountId,
+};
+
+type Balance = <DefaultEnvironment as ink::env::Environment>::Balance;
+
+/// Error returned by PSP22 token contracts, as defined by the PSP22 standard.
+#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+/// Calls `PSP22::transfer` on `token`, sending `value` from the calling contract to `to`.
+/// A call that traps or cannot be decoded is reported as `PSP22Error::Custom`.
+pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> Result<(), PSP22Error> {
+    let result = build_call::<DefaultEnvironment>()
+        .call(token)
+        .gas_limit(0)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                .push_arg(to)
+                .push_arg(value)
+                .push_arg(Vec::<u8>::new()),
+        )
+        .returns::<Result<(), PSP22Error>>()
+        .try_invoke();
+
+    match result {
+        Ok(Ok(result)) => result,
+        _ => Err(PSP22Error::Custom(String::from("Token call failed"))),
+    }
+}
+
+/// Calls `PSP22::transfer_from` on `token`, moving `value` from `from` to `to` using the
+/// allowance granted to the calling contract.
+pub fn transfer_from(
+    token: AccountId,
+    from: AccountId,
+    to: AccountId,
+    value: Balance,
+) -> Result<(), PSP22Error> {
+    build_call::<DefaultEnvironment>()
+        .call(token)
+        .gas_limit(0)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                .push_arg(from)
+                .push_arg(to)
+                .push_arg(value)
+                .push_arg(Vec::<u8>::new()),
+        )
+        .returns::<Result<(), PSP22Error>>()
+        .invoke()
+}
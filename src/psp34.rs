@@ -0,0 +1,68 @@
+//! PSP34 (non-fungible token) interface, as defined by the PSP34 standard.
+
+use ink::{
+    env::{DefaultEnvironment, Environment},
+    prelude::{string::String, vec::Vec},
+    primitives::AccountId,
+};
+
+type Balance = <DefaultEnvironment as Environment>::Balance;
+
+/// Identifier of a PSP34 token.
+#[derive(scale::Encode, scale::Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// Error returned by PSP34 messages.
+#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+#[ink::trait_definition]
+pub trait PSP34 {
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    #[ink(message)]
+    fn approve(
+        &mut self,
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+}
+
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}